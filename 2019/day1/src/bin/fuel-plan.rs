@@ -0,0 +1,50 @@
+use advent2019_day1::fuel::{best_selection, max_mass_for_fuel, Manifest, Mass};
+use failure::{bail, format_err, Error};
+use num_bigint::BigInt;
+use std::env;
+use std::path::Path;
+
+const USAGE: &str = "usage: fuel-plan [--big] BUDGET [MANIFEST]";
+
+fn plan<T: Mass>(budget: &str, manifest: Option<&str>) -> Result<(), Error> {
+    let budget = T::parse_mass(budget)?;
+    match manifest {
+        None => {
+            let mass = max_mass_for_fuel(&budget)
+                .ok_or_else(|| format_err!("Budget must not be negative"))?;
+            println!("largest module: {}", mass);
+        }
+        Some(path) => {
+            let manifest = Manifest::<T>::load(Path::new(path))?;
+            let selection = best_selection(&manifest.modules, &budget).ok_or_else(|| {
+                if budget < T::zero() {
+                    format_err!("Budget must not be negative")
+                } else {
+                    format_err!("Total mass is too big; try --big")
+                }
+            })?;
+            for i in &selection.modules {
+                let module = &manifest.modules[*i];
+                println!("  {:<20} {:>12}", module.name, module.mass);
+            }
+            println!("mass: {}", selection.mass);
+            println!("fuel: {} of {}", selection.fuel, budget);
+        }
+    }
+    Ok(())
+}
+
+// With just a fuel budget, find the largest module whose fuel fits in it.
+// With a manifest too, choose the modules with the greatest total mass whose
+// fuel fits.
+fn main() -> Result<(), Error> {
+    let args: Vec<String> = env::args().skip(1).collect();
+    let args: Vec<&str> = args.iter().map(String::as_str).collect();
+    match args.as_slice() {
+        ["--big", budget, rest @ ..] if rest.len() <= 1 => {
+            plan::<BigInt>(budget, rest.first().cloned())
+        }
+        [budget, rest @ ..] if rest.len() <= 1 => plan::<i64>(budget, rest.first().cloned()),
+        _ => bail!("{}", USAGE),
+    }
+}
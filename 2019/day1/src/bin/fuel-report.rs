@@ -0,0 +1,21 @@
+use advent2019_day1::fuel::{report, Manifest};
+use failure::{bail, Error};
+use num_bigint::BigInt;
+use std::env;
+use std::path::Path;
+
+const USAGE: &str = "usage: fuel-report [--big] MANIFEST";
+
+// Report the fuel needed for each module and stage in a manifest: a CSV or
+// TOML file of named modules, or a bare list of masses like the puzzle input.
+// With --big, masses can be arbitrarily large.
+fn main() -> Result<(), Error> {
+    let args: Vec<String> = env::args().skip(1).collect();
+    let args: Vec<&str> = args.iter().map(String::as_str).collect();
+    match args.as_slice() {
+        [path] => print!("{}", report(&Manifest::<i64>::load(Path::new(path))?)?),
+        ["--big", path] => print!("{}", report(&Manifest::<BigInt>::load(Path::new(path))?)?),
+        _ => bail!("{}", USAGE),
+    }
+    Ok(())
+}
@@ -0,0 +1,678 @@
+use common::parse::parse_lines;
+use failure::{bail, format_err, Error};
+use num_integer::Integer;
+use num_traits::CheckedAdd;
+use std::fmt;
+use std::fs;
+use std::path::Path;
+use std::str::FromStr;
+use toml::Value;
+
+// Any integer type can be a mass, including `num_bigint::BigInt` for masses
+// too big for 64 bits.
+pub trait Mass: Integer + CheckedAdd + Clone + From<u8> + fmt::Display {
+    fn parse_mass(mass: &str) -> Result<Self, Error>;
+}
+
+impl<T> Mass for T
+where
+    T: Integer + CheckedAdd + Clone + From<u8> + fmt::Display + FromStr,
+    T::Err: fmt::Display,
+{
+    fn parse_mass(mass: &str) -> Result<T, Error> {
+        mass.trim()
+            .parse()
+            .map_err(|e| format_err!("Failed to parse mass {}: {}", mass, e))
+    }
+}
+
+pub fn fuel_for_module<T: Mass>(mass: &T) -> T {
+    // Unsigned masses can't go negative, so compare before subtracting.
+    let third = mass.clone() / T::from(3);
+    let two = T::from(2);
+    if third > two {
+        third - two
+    } else {
+        T::zero()
+    }
+}
+
+// Fuel also needs fuel to carry it. Each extra lot of fuel is a third of the
+// last, so this only loops O(log mass) times.
+pub fn fuel_for_mass<T: Mass>(mass: &T) -> T {
+    let mut total = T::zero();
+    let mut fuel = fuel_for_module(mass);
+    while !fuel.is_zero() {
+        let next = fuel_for_module(&fuel);
+        total = total + fuel;
+        fuel = next;
+    }
+    total
+}
+
+// One mass per line, as in the puzzle input.
+pub fn parse_masses<T: Mass>(text: &str) -> Result<Vec<T>, Error> {
+    parse_lines(text, T::parse_mass)
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Module<T = i64> {
+    pub name: String,
+    pub mass: T,
+    pub stage: Option<String>,
+}
+
+// The modules making up a rocket, optionally grouped into stages.
+#[derive(Debug, PartialEq)]
+pub struct Manifest<T = i64> {
+    pub modules: Vec<Module<T>>,
+}
+
+impl<T: Mass> Manifest<T> {
+    // Modules are named by line.
+    pub fn parse_masses(text: &str) -> Result<Manifest<T>, Error> {
+        let modules = parse_masses(text)?
+            .into_iter()
+            .enumerate()
+            .map(|(i, mass)| Module {
+                name: format!("module {}", i + 1),
+                mass,
+                stage: None,
+            })
+            .collect();
+        Ok(Manifest { modules })
+    }
+
+    // `name,mass[,stage]` per line. Blank lines and `#` comments are
+    // skipped.
+    pub fn parse_csv(text: &str) -> Result<Manifest<T>, Error> {
+        let mut modules = vec![];
+        for (i, line) in text.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let fields: Vec<&str> = line.split(',').map(str::trim).collect();
+            let (name, mass, stage) = match fields[..] {
+                [name, mass] => (name, mass, None),
+                [name, mass, stage] => (name, mass, Some(stage.to_string())),
+                _ => bail!("Line {}: expected name,mass[,stage]: {:?}", i + 1, line),
+            };
+            let mass = T::parse_mass(mass).map_err(|e| format_err!("Line {}: {}", i + 1, e))?;
+            modules.push(Module {
+                name: name.to_string(),
+                mass,
+                stage,
+            });
+        }
+        Ok(Manifest { modules })
+    }
+
+    // Modules are `name = mass`, either at the top level or in a table per
+    // stage:
+    //
+    //     [booster]
+    //     engine = 12345
+    //
+    // Masses too big for a TOML integer can be given as strings.
+    pub fn parse_toml(text: &str) -> Result<Manifest<T>, Error> {
+        let manifest: Value = text.parse()?;
+        let mut modules = vec![];
+        let module = |name: &str, mass: &Value, stage: Option<&str>| {
+            let mass = match mass {
+                Value::Integer(mass) => T::parse_mass(&mass.to_string()),
+                Value::String(mass) => T::parse_mass(mass),
+                _ => bail!("Mass of {} must be an integer", name),
+            };
+            Ok(Module {
+                name: name.to_string(),
+                mass: mass.map_err(|e| format_err!("{}: {}", name, e))?,
+                stage: stage.map(str::to_string),
+            })
+        };
+        for (name, value) in manifest.as_table().into_iter().flatten() {
+            match value {
+                Value::Table(stage) => {
+                    for (module_name, mass) in stage {
+                        modules.push(module(module_name, mass, Some(name))?);
+                    }
+                }
+                mass => modules.push(module(name, mass, None)?),
+            }
+        }
+        Ok(Manifest { modules })
+    }
+
+    // Choose the format from the file extension: `.csv`, `.toml`, or else a
+    // bare list of masses.
+    pub fn load(path: &Path) -> Result<Manifest<T>, Error> {
+        let text = fs::read_to_string(path)
+            .map_err(|e| format_err!("Failed to read {}: {}", path.display(), e))?;
+        let manifest = match path.extension().and_then(|e| e.to_str()) {
+            Some("csv") => Manifest::parse_csv(&text),
+            Some("toml") => Manifest::parse_toml(&text),
+            _ => Manifest::parse_masses(&text),
+        };
+        manifest.map_err(|e| format_err!("{}: {}", path.display(), e))
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Fuel<T = i64> {
+    pub mass: T,
+    // Fuel for the module alone, as in part 1.
+    pub naive: T,
+    // Fuel including the fuel to carry the fuel, as in part 2.
+    pub recursive: T,
+}
+
+impl<T: Mass> Fuel<T> {
+    fn zero() -> Fuel<T> {
+        Fuel {
+            mass: T::zero(),
+            naive: T::zero(),
+            recursive: T::zero(),
+        }
+    }
+
+    fn for_mass(mass: &T) -> Fuel<T> {
+        Fuel {
+            mass: mass.clone(),
+            naive: fuel_for_module(mass),
+            recursive: fuel_for_mass(mass),
+        }
+    }
+
+    fn add(self, other: &Fuel<T>) -> Result<Fuel<T>, Error> {
+        let sum = |a: &T, b: &T| {
+            a.checked_add(b)
+                .ok_or_else(|| format_err!("Total mass is too big; try --big"))
+        };
+        Ok(Fuel {
+            mass: sum(&self.mass, &other.mass)?,
+            naive: sum(&self.naive, &other.naive)?,
+            recursive: sum(&self.recursive, &other.recursive)?,
+        })
+    }
+}
+
+pub struct Stage<T = i64> {
+    pub name: Option<String>,
+    pub modules: Vec<(String, Fuel<T>)>,
+    pub total: Fuel<T>,
+}
+
+// Fuel per module, per stage and in total. Stages are in the order they're
+// first listed in the manifest.
+pub struct Report<T = i64> {
+    pub stages: Vec<Stage<T>>,
+    pub total: Fuel<T>,
+}
+
+pub fn report<T: Mass>(manifest: &Manifest<T>) -> Result<Report<T>, Error> {
+    let mut stages: Vec<Stage<T>> = vec![];
+    for module in &manifest.modules {
+        let fuel = Fuel::for_mass(&module.mass);
+        let i = match stages.iter().position(|s| s.name == module.stage) {
+            Some(i) => i,
+            None => {
+                stages.push(Stage {
+                    name: module.stage.clone(),
+                    modules: vec![],
+                    total: Fuel::zero(),
+                });
+                stages.len() - 1
+            }
+        };
+        let stage = &mut stages[i];
+        stage.total = stage.total.clone().add(&fuel)?;
+        stage.modules.push((module.name.clone(), fuel));
+    }
+    let mut total = Fuel::zero();
+    for stage in &stages {
+        total = total.add(&stage.total)?;
+    }
+    Ok(Report { stages, total })
+}
+
+fn write_row<T: Mass>(f: &mut fmt::Formatter, name: &str, fuel: &Fuel<T>) -> fmt::Result {
+    writeln!(
+        f,
+        "  {:<20} {:>12} {:>12} {:>12}",
+        name, fuel.mass, fuel.naive, fuel.recursive
+    )
+}
+
+impl<T: Mass> fmt::Display for Report<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let staged = self.stages.iter().any(|s| s.name.is_some());
+        writeln!(
+            f,
+            "  {:<20} {:>12} {:>12} {:>12}",
+            "module", "mass", "fuel", "total fuel"
+        )?;
+        for stage in &self.stages {
+            if staged {
+                writeln!(f, "{}", stage.name.as_deref().unwrap_or("(no stage)"))?;
+            }
+            for (name, fuel) in &stage.modules {
+                write_row(f, name, fuel)?;
+            }
+            if staged {
+                write_row(f, "subtotal", &stage.total)?;
+            }
+        }
+        write_row(f, "total", &self.total)
+    }
+}
+
+// The largest mass whose fuel, including fuel for the fuel, fits in the
+// budget, or None if the budget is negative. Fuel never decreases as mass
+// increases, so this adds on a step that doubles for as long as the mass
+// still fits, then halves it again. Every sum is checked, so a budget too
+// big for any mass of the type to use up gives the type's largest mass.
+pub fn max_mass_for_fuel<T: Mass>(budget: &T) -> Option<T> {
+    if *budget < T::zero() {
+        return None;
+    }
+    let fits = |mass: &T| fuel_for_mass(mass) <= *budget;
+    let mut mass = T::zero();
+    let mut step = T::one();
+    while let Some(next) = mass.checked_add(&step).filter(&fits) {
+        mass = next;
+        match step.checked_add(&step) {
+            Some(double) => step = double,
+            None => break,
+        }
+    }
+    while !step.is_zero() {
+        if let Some(next) = mass.checked_add(&step).filter(&fits) {
+            mass = next;
+        }
+        step = step / T::from(2);
+    }
+    Some(mass)
+}
+
+// Modules chosen from a fleet, by index, with their total mass and fuel.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Selection<T = i64> {
+    pub modules: Vec<usize>,
+    pub mass: T,
+    pub fuel: T,
+}
+
+// The modules with the greatest total mass whose total fuel fits in the
+// budget (a 0/1 knapsack). Rather than a table over every possible fuel
+// total, this keeps only the selections that no other selection beats on
+// both fuel and mass, so it works for budgets of any size. None if the
+// budget is negative or the best total mass is too big for the mass type.
+pub fn best_selection<T: Mass>(fleet: &[Module<T>], budget: &T) -> Option<Selection<T>> {
+    if *budget < T::zero() {
+        return None;
+    }
+    // Ordered by increasing fuel and strictly increasing mass.
+    let mut best = vec![Selection {
+        modules: vec![],
+        mass: T::zero(),
+        fuel: T::zero(),
+    }];
+    for (i, module) in fleet.iter().enumerate() {
+        let fuel = fuel_for_mass(&module.mass);
+        let mut merged = best.clone();
+        for s in &best {
+            // Fuel that overflows is over the budget too.
+            let total = match s.fuel.checked_add(&fuel) {
+                Some(total) if total <= *budget => total,
+                _ => continue,
+            };
+            // If any selection that fits overflows, so does the best one.
+            let mass = s.mass.checked_add(&module.mass)?;
+            let mut modules = s.modules.clone();
+            modules.push(i);
+            merged.push(Selection {
+                modules,
+                mass,
+                fuel: total,
+            });
+        }
+        merged.sort_by(|a, b| a.fuel.cmp(&b.fuel).then(b.mass.cmp(&a.mass)));
+        best = vec![];
+        for s in merged {
+            if best
+                .last()
+                .is_none_or(|last: &Selection<T>| s.mass > last.mass)
+            {
+                best.push(s);
+            }
+        }
+    }
+    best.pop()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use common::random::Random;
+    use num_bigint::BigInt;
+
+    // The original recursive definition, to check the iterative one against.
+    fn fuel_for_mass_recursive<T: Mass>(mass: &T) -> T {
+        let fuel = fuel_for_module(mass);
+        if fuel.is_zero() {
+            fuel
+        } else {
+            fuel_for_mass_recursive(&fuel) + fuel
+        }
+    }
+
+    #[test]
+    fn test_fuel_for_module() {
+        assert!(fuel_for_module(&12) == 2);
+        assert!(fuel_for_module(&14) == 2);
+        assert!(fuel_for_module(&1969) == 654);
+        assert!(fuel_for_module(&100756) == 33583);
+        assert!(fuel_for_module(&5u32) == 0);
+    }
+
+    #[test]
+    fn test_fuel_for_mass() {
+        assert!(fuel_for_mass(&14) == 2);
+        assert!(fuel_for_mass(&1969) == 966);
+        assert!(fuel_for_mass(&100756) == 50346);
+        assert!(fuel_for_mass(&100756u32) == 50346);
+        assert!(fuel_for_mass(&BigInt::from(100756)) == BigInt::from(50346));
+    }
+
+    #[test]
+    fn test_fuel_for_mass_matches_recursive() {
+        for mass in 0..=10_000_000i64 {
+            assert_eq!(
+                fuel_for_mass(&mass),
+                fuel_for_mass_recursive(&mass),
+                "mass {}",
+                mass
+            );
+        }
+    }
+
+    #[test]
+    fn test_huge_masses() {
+        let mass = i64::MAX;
+        assert_eq!(fuel_for_mass(&mass), fuel_for_mass_recursive(&mass));
+        assert_eq!(
+            BigInt::from(fuel_for_mass(&mass)),
+            fuel_for_mass(&BigInt::from(mass))
+        );
+
+        let mass: BigInt = "1000000000000000000000000000000".parse().unwrap();
+        assert_eq!(fuel_for_mass(&mass), fuel_for_mass_recursive(&mass));
+    }
+
+    #[test]
+    fn test_parse_masses() {
+        assert_eq!(parse_masses::<i64>("12\n14").unwrap(), vec![12, 14]);
+        assert_eq!(
+            parse_masses::<i64>("12\n14\nfourteen")
+                .err()
+                .unwrap()
+                .to_string(),
+            "Line 3: Failed to parse mass fourteen: invalid digit found in string"
+        );
+        assert!(parse_masses::<i64>("99999999999999999999").is_err());
+        assert_eq!(
+            parse_masses::<BigInt>("99999999999999999999").unwrap(),
+            vec!["99999999999999999999".parse().unwrap()]
+        );
+    }
+
+    const CSV: &str = "\
+                       # name,mass,stage\n\
+                       engine,100756,booster\n\
+                       tank,1969,booster\n\
+                       \n\
+                       comms,14,payload\n\
+                       probe,12";
+
+    #[test]
+    fn test_parse_csv() {
+        let manifest: Manifest = Manifest::parse_csv(CSV).unwrap();
+        assert_eq!(manifest.modules.len(), 4);
+        assert_eq!(
+            manifest.modules[2],
+            Module {
+                name: "comms".to_string(),
+                mass: 14,
+                stage: Some("payload".to_string()),
+            }
+        );
+        assert_eq!(manifest.modules[3].stage, None);
+    }
+
+    #[test]
+    fn test_parse_toml() {
+        let toml = "probe = 12\n\
+                    [booster]\n\
+                    engine = 100756\n\
+                    tank = 1969\n\
+                    [payload]\n\
+                    comms = 14";
+        let mut from_csv = Manifest::<i64>::parse_csv(CSV).unwrap().modules;
+        let mut from_toml = Manifest::parse_toml(toml).unwrap().modules;
+        from_csv.sort_by(|a, b| a.name.cmp(&b.name));
+        from_toml.sort_by(|a, b| a.name.cmp(&b.name));
+        assert_eq!(from_csv, from_toml);
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        let test_data = [
+            (
+                "engine,100756\ntank",
+                "Line 2: expected name,mass[,stage]: \"tank\"",
+            ),
+            (
+                "engine,100756\ntank,lots",
+                "Line 2: Failed to parse mass lots: invalid digit found in string",
+            ),
+        ];
+
+        for (csv, expected) in &test_data {
+            assert_eq!(
+                Manifest::<i64>::parse_csv(csv).err().unwrap().to_string(),
+                *expected
+            );
+        }
+        assert_eq!(
+            Manifest::<i64>::parse_toml("[booster]\nengine = \"big\"")
+                .err()
+                .unwrap()
+                .to_string(),
+            "engine: Failed to parse mass big: invalid digit found in string"
+        );
+        assert_eq!(
+            Manifest::<i64>::parse_toml("[booster]\nengine = 1.5")
+                .err()
+                .unwrap()
+                .to_string(),
+            "Mass of engine must be an integer"
+        );
+    }
+
+    #[test]
+    fn test_big_manifest() {
+        let toml = "[booster]\n\
+                    engine = \"1000000000000000000000000000000\"\n\
+                    tank = 1969";
+        let report = report(&Manifest::<BigInt>::parse_toml(toml).unwrap()).unwrap();
+        let engine: BigInt = "1000000000000000000000000000000".parse().unwrap();
+        assert_eq!(report.total.mass, engine.clone() + 1969);
+        assert_eq!(report.total.recursive, fuel_for_mass(&engine) + 966);
+    }
+
+    #[test]
+    fn test_max_mass_for_fuel() {
+        assert_eq!(max_mass_for_fuel(&-1), None);
+        assert_eq!(max_mass_for_fuel(&0), Some(8));
+        assert_eq!(max_mass_for_fuel(&966), Some(1970));
+        assert_eq!(max_mass_for_fuel(&0u32), Some(8));
+
+        // Check against a linear scan.
+        let mut mass = 0;
+        for budget in 0..5000 {
+            while fuel_for_mass(&(mass + 1)) <= budget {
+                mass += 1;
+            }
+            assert_eq!(max_mass_for_fuel(&budget), Some(mass), "budget {}", budget);
+        }
+
+        let budget: BigInt = "1000000000000000000000000000000".parse().unwrap();
+        let mass = max_mass_for_fuel(&budget).unwrap();
+        assert!(fuel_for_mass(&mass) <= budget);
+        assert!(fuel_for_mass(&(mass + 1)) > budget);
+    }
+
+    #[test]
+    fn test_max_mass_for_huge_budgets() {
+        // Even the largest mass doesn't use up these budgets.
+        assert_eq!(max_mass_for_fuel(&i64::MAX), Some(i64::MAX));
+        assert_eq!(max_mass_for_fuel(&u32::MAX), Some(u32::MAX));
+
+        let budget = 4_000_000_000_000_000_000_i64;
+        let mass = max_mass_for_fuel(&budget).unwrap();
+        assert!(fuel_for_mass(&mass) <= budget);
+        assert!(fuel_for_mass(&(mass + 1)) > budget);
+        assert_eq!(
+            BigInt::from(mass),
+            max_mass_for_fuel(&BigInt::from(budget)).unwrap()
+        );
+    }
+
+    fn fleet(masses: &[i64]) -> Vec<Module> {
+        masses
+            .iter()
+            .enumerate()
+            .map(|(i, mass)| Module {
+                name: format!("module {}", i + 1),
+                mass: *mass,
+                stage: None,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_best_selection() {
+        // Fuel 2, 966, 50346 and 808.
+        let fleet = fleet(&[14, 1969, 100756, 1654]);
+        let test_data = [
+            (0, vec![], 0),
+            (1000, vec![0, 1], 14 + 1969),
+            (1775, vec![1, 3], 1969 + 1654),
+            (1776, vec![0, 1, 3], 14 + 1969 + 1654),
+            (50346, vec![2], 100756),
+        ];
+
+        for (budget, modules, mass) in &test_data {
+            let selection = best_selection(&fleet, budget).unwrap();
+            assert_eq!(selection.modules, *modules, "budget {}", budget);
+            assert_eq!(selection.mass, *mass);
+            assert!(selection.fuel <= *budget);
+        }
+    }
+
+    #[test]
+    fn test_best_selection_limits() {
+        let small = fleet(&[14, 1969]);
+        assert_eq!(best_selection(&small, &-1), None);
+
+        // Two of these fit in the budget but their total mass doesn't fit
+        // in 64 bits.
+        let big = fleet(&[i64::MAX / 2 + 1, i64::MAX / 2 + 1]);
+        assert_eq!(best_selection(&big, &i64::MAX), None);
+        let big: Vec<Module<BigInt>> = big
+            .into_iter()
+            .map(|m| Module {
+                name: m.name,
+                mass: BigInt::from(m.mass),
+                stage: m.stage,
+            })
+            .collect();
+        let selection = best_selection(&big, &BigInt::from(i64::MAX)).unwrap();
+        assert_eq!(selection.mass, BigInt::from(i64::MAX) + 1);
+    }
+
+    #[test]
+    fn test_best_selection_matches_brute_force() {
+        let mut random = Random::new(12345);
+        for _ in 0..50 {
+            let masses: Vec<i64> = (0..10).map(|_| random.below(100_000) as i64).collect();
+            let fleet = fleet(&masses);
+            let budget = random.below(100_000) as i64;
+
+            let fuels: Vec<i64> = masses.iter().map(fuel_for_mass).collect();
+            let best_mass = (0..1 << masses.len())
+                .filter(|subset| {
+                    let fuel: i64 = (0..masses.len())
+                        .filter(|i| subset & (1 << i) != 0)
+                        .map(|i| fuels[i])
+                        .sum();
+                    fuel <= budget
+                })
+                .map(|subset| {
+                    (0..masses.len())
+                        .filter(|i| subset & (1 << i) != 0)
+                        .map(|i| masses[i])
+                        .sum::<i64>()
+                })
+                .max()
+                .unwrap();
+
+            let selection = best_selection(&fleet, &budget).unwrap();
+            assert_eq!(selection.mass, best_mass);
+            assert!(selection.fuel <= budget);
+        }
+    }
+
+    #[test]
+    fn test_report() {
+        let report = report(&Manifest::<i64>::parse_csv(CSV).unwrap()).unwrap();
+        let booster = &report.stages[0];
+        assert_eq!(booster.name.as_deref(), Some("booster"));
+        assert_eq!(
+            booster.total,
+            Fuel {
+                mass: 100756 + 1969,
+                naive: 33583 + 654,
+                recursive: 50346 + 966,
+            }
+        );
+        assert_eq!(report.stages.len(), 3);
+        assert_eq!(
+            report.total,
+            Fuel {
+                mass: 100756 + 1969 + 14 + 12,
+                naive: 33583 + 654 + 2 + 2,
+                recursive: 50346 + 966 + 2 + 2,
+            }
+        );
+    }
+
+    #[test]
+    fn test_report_too_big() {
+        let toml = "[booster]\nengine = 9000000000000000000\n\
+                    [payload]\nprobe = 9000000000000000000";
+        assert_eq!(
+            report(&Manifest::<i64>::parse_toml(toml).unwrap())
+                .err()
+                .unwrap()
+                .to_string(),
+            "Total mass is too big; try --big"
+        );
+        let report = report(&Manifest::<BigInt>::parse_toml(toml).unwrap()).unwrap();
+        assert_eq!(
+            report.total.mass,
+            BigInt::from(18_000_000_000_000_000_000u64)
+        );
+    }
+}
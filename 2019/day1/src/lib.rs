@@ -0,0 +1,37 @@
+use failure::Error;
+use solution::{Answer, Solution};
+
+pub mod fuel;
+
+use fuel::{fuel_for_mass, fuel_for_module, parse_masses};
+
+pub struct Day1;
+
+impl Solution for Day1 {
+    type Input = Vec<i64>;
+
+    fn parse(input: &str) -> Result<Vec<i64>, Error> {
+        parse_masses(input)
+    }
+
+    fn part1(masses: &Vec<i64>) -> Answer {
+        masses.iter().map(fuel_for_module).sum::<i64>().into()
+    }
+
+    fn part2(masses: &Vec<i64>) -> Answer {
+        masses.iter().map(fuel_for_mass).sum::<i64>().into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    solution::example_tests!(Day1, 2019, 1);
+
+    #[test]
+    fn test_parse() {
+        assert_eq!(Day1::parse("12\n14").unwrap(), vec![12, 14]);
+        assert!(Day1::parse("12\nfourteen").is_err());
+    }
+}
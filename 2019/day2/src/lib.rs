@@ -1,5 +1,6 @@
+use common::parse::parse_list;
 use failure::Error;
-use std::fs;
+use solution::{Answer, Solution};
 
 type MemCell = u32;
 
@@ -64,41 +65,48 @@ impl Computer {
         self.mem[pos] = val;
     }
 
-    fn read_mem(&mut self, pos: usize) -> MemCell {
+    fn read_mem(&self, pos: usize) -> MemCell {
         self.mem[pos]
     }
+}
 
-    fn _core_dump(&self) {
-        println!("{:?}", self.mem)
-    }
+fn load_program(prog: &str) -> Result<Vec<MemCell>, Error> {
+    parse_list(prog, ',', "memory cell")
 }
 
-fn load_program(prog: &str) -> Vec<MemCell> {
-    prog.split(',')
-        .map(|i| i.parse::<MemCell>().unwrap())
-        .collect()
+// The output of the program is left in address 0.
+fn run_program(program: &[MemCell], noun: MemCell, verb: MemCell) -> MemCell {
+    let mut computer = Computer::new(program.to_vec());
+    computer.write_mem(1, noun);
+    computer.write_mem(2, verb);
+    computer.exec().read_mem(0)
 }
 
-fn main() -> Result<(), Error> {
-    const TARGET_OUTPUT: MemCell = 19_690_720;
-
-    let program = load_program(&fs::read_to_string("data.txt")?);
-
-    'outer: for noun in 0..99 {
-        for verb in 0..99 {
-            let mut computer = Computer::new(program.clone());
-            computer.write_mem(1, noun);
-            computer.write_mem(2, verb);
-            let mut computer = computer.exec();
-            if computer.read_mem(0) == TARGET_OUTPUT {
-                println!("noun: {}, verb:{}", noun, verb);
-                println!("Answer: {}", (100 * noun) + verb);
-                break 'outer;
+pub struct Day2;
+
+impl Solution for Day2 {
+    type Input = Vec<MemCell>;
+
+    fn parse(input: &str) -> Result<Vec<MemCell>, Error> {
+        load_program(input)
+    }
+
+    fn part1(program: &Vec<MemCell>) -> Answer {
+        run_program(program, 12, 2).into()
+    }
+
+    fn part2(program: &Vec<MemCell>) -> Answer {
+        const TARGET_OUTPUT: MemCell = 19_690_720;
+
+        for noun in 0..99 {
+            for verb in 0..99 {
+                if run_program(program, noun, verb) == TARGET_OUTPUT {
+                    return ((100 * noun) + verb).into();
+                }
             }
         }
+        panic!("No noun and verb give {}", TARGET_OUTPUT)
     }
-
-    Ok(())
 }
 
 #[cfg(test)]
@@ -116,8 +124,8 @@ mod tests {
 
         for (start_state, end_state) in &test_data {
             assert_eq!(
-                Computer::new(load_program(start_state)).exec().mem,
-                load_program(end_state)
+                Computer::new(load_program(start_state).unwrap()).exec().mem,
+                load_program(end_state).unwrap()
             );
         }
     }
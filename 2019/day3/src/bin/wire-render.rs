@@ -0,0 +1,39 @@
+use advent2019_day3::render;
+use advent2019_day3::wire::{parse_layout, Metric, StepPolicy};
+use failure::{bail, format_err, Error};
+use solution::inputs::read_input;
+use std::env;
+use std::path::Path;
+
+const USAGE: &str = "usage: wire-render [--metric manhattan|chebyshev|euclidean|steps|max-steps] \
+                     [--steps first|last|every] [--ascii | --colour] FILE";
+
+// Draw the wires in a file, one per line, as SVG on stdout, or as text for
+// small layouts, highlighting the best point where they all cross.
+fn main() -> Result<(), Error> {
+    let mut path = None;
+    let mut metric = Metric::Manhattan;
+    let mut policy = StepPolicy::FirstVisit;
+    let mut text = None;
+
+    let mut args = env::args().skip(1);
+    while let Some(arg) = args.next() {
+        let mut value = || args.next().ok_or_else(|| format_err!("{}", USAGE));
+        match arg.as_str() {
+            "--metric" => metric = value()?.parse()?,
+            "--steps" => policy = value()?.parse()?,
+            "--ascii" if text.is_none() => text = Some(false),
+            "--colour" if text.is_none() => text = Some(true),
+            _ if arg.starts_with("--") || path.is_some() => bail!("{}", USAGE),
+            _ => path = Some(arg),
+        }
+    }
+    let path = path.ok_or_else(|| format_err!("{}", USAGE))?;
+    let wires = parse_layout(&read_input(Path::new(&path))?)?;
+
+    match text {
+        Some(colour) => print!("{}", render::ascii(&wires, metric, policy, colour)?),
+        None => print!("{}", render::svg(&wires, metric, policy)),
+    }
+    Ok(())
+}
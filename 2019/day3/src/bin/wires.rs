@@ -0,0 +1,52 @@
+use advent2019_day3::wire::{best_crossing, pairwise, parse_layout, Metric, StepPolicy};
+use failure::{bail, format_err, Error};
+use solution::inputs::read_input;
+use std::env;
+use std::path::Path;
+
+const USAGE: &str =
+    "usage: wires [--metric manhattan|chebyshev|euclidean|steps|max-steps] [--steps first|last|every] FILE";
+
+// Report where each wire in a file, one per line, crosses itself, the best
+// crossing of each pair of wires, and the best point where they all cross.
+fn main() -> Result<(), Error> {
+    let mut path = None;
+    let mut metric = Metric::Manhattan;
+    let mut policy = StepPolicy::FirstVisit;
+
+    let mut args = env::args().skip(1);
+    while let Some(arg) = args.next() {
+        let mut value = || args.next().ok_or_else(|| format_err!("{}", USAGE));
+        match arg.as_str() {
+            "--metric" => metric = value()?.parse()?,
+            "--steps" => policy = value()?.parse()?,
+            _ if arg.starts_with("--") || path.is_some() => bail!("{}", USAGE),
+            _ => path = Some(arg),
+        }
+    }
+    let path = path.ok_or_else(|| format_err!("{}", USAGE))?;
+    let wires = parse_layout(&read_input(Path::new(&path))?)?;
+
+    for (i, wire) in wires.iter().enumerate() {
+        for c in wire.self_crossings() {
+            println!("wire {} crosses itself: {}", i + 1, c);
+        }
+    }
+    for ((a, b), crossing) in pairwise(&wires, metric, policy) {
+        match crossing {
+            Some(c) => println!(
+                "wires {} and {}: {} at {}",
+                a + 1,
+                b + 1,
+                c.score(metric),
+                c
+            ),
+            None => println!("wires {} and {}: never cross", a + 1, b + 1),
+        }
+    }
+    match best_crossing(&wires, metric, policy) {
+        Some(c) => println!("all wires: {} at {}", c.score(metric), c),
+        None => println!("all wires: never all cross"),
+    }
+    Ok(())
+}
@@ -0,0 +1,109 @@
+use failure::{bail, Error};
+use solution::{Answer, Solution};
+use std::convert::TryFrom;
+
+pub mod path;
+pub mod render;
+pub mod wire;
+
+use wire::{best_crossing, parse_layout, Metric, StepPolicy, Wire};
+
+fn closest(wires: &[Wire], metric: Metric) -> Answer {
+    match best_crossing(wires, metric, StepPolicy::FirstVisit) {
+        Some(crossing) => {
+            let score = crossing.score(metric);
+            match i64::try_from(score) {
+                Ok(score) => score.into(),
+                Err(_) => score.to_string().into(),
+            }
+        }
+        None => "The wires never all cross".to_string().into(),
+    }
+}
+
+pub struct Day3;
+
+impl Solution for Day3 {
+    type Input = Vec<Wire>;
+
+    fn parse(layout: &str) -> Result<Vec<Wire>, Error> {
+        let wires = parse_layout(layout)?;
+        if wires.len() < 2 {
+            bail!("Expected at least 2 wires, found {}", wires.len());
+        }
+        Ok(wires)
+    }
+
+    fn part1(wires: &Vec<Wire>) -> Answer {
+        closest(wires, Metric::Manhattan)
+    }
+
+    fn part2(wires: &Vec<Wire>) -> Answer {
+        closest(wires, Metric::CombinedSteps)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    solution::example_tests!(Day3, 2019, 3);
+
+    #[test]
+    fn test_long_wires() {
+        let wires = Day3::parse(
+            "R4000000000,U4000000000\n\
+             U1000000000,R5000000000,D2000000000",
+        )
+        .unwrap();
+        assert_eq!(Day3::part1(&wires), Answer::Int(5_000_000_000));
+        assert_eq!(Day3::part2(&wires), Answer::Int(10_000_000_000));
+
+        // They cross once, at (4000000000, 1000000000).
+        let test_data = [
+            (Metric::Manhattan, 5_000_000_000),
+            (Metric::Chebyshev, 4_000_000_000),
+            (Metric::Euclidean, 17_000_000_000_000_000_000),
+            (Metric::CombinedSteps, 10_000_000_000),
+            (Metric::MaxSteps, 5_000_000_000),
+        ];
+        for (metric, expected) in &test_data {
+            let best = best_crossing(&wires, *metric, StepPolicy::FirstVisit).unwrap();
+            assert_eq!(best.score(*metric), *expected, "{:?}", metric);
+        }
+    }
+
+    #[test]
+    fn test_never_cross() {
+        let wires = Day3::parse("R8,U5\nL8,D5").unwrap();
+        assert_eq!(
+            Day3::part1(&wires),
+            Answer::Text("The wires never all cross".to_string())
+        );
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        let test_data = [
+            ("R8,U5", "Expected at least 2 wires, found 1"),
+            ("R8,X5\nU7", "Line 1: Invalid direction \"X5\" at column 4"),
+            (
+                "R8\nU7,U",
+                "Line 2: Invalid length \"U\": cannot parse integer from empty string at column 4",
+            ),
+            (
+                "R8,NE-5\nU7",
+                "Line 1: Invalid length \"NE-5\": must not be negative at column 4",
+            ),
+            (
+                "U7\nR9223372036854775807,R1",
+                "Line 2: Invalid length \"R9223372036854775807\": path is longer than \
+                 1152921504606846976 steps at column 1",
+            ),
+        ];
+
+        for (layout, expected) in &test_data {
+            assert_eq!(Day3::parse(layout).err().unwrap().to_string(), *expected);
+        }
+    }
+}
@@ -0,0 +1,179 @@
+use failure::{bail, format_err, Error};
+
+// The eight compass directions, anticlockwise from east, as steps on the grid.
+const HEADINGS: [(i64, i64); 8] = [
+    (1, 0),
+    (1, 1),
+    (0, 1),
+    (-1, 1),
+    (-1, 0),
+    (-1, -1),
+    (0, -1),
+    (1, -1),
+];
+
+fn heading(token: &str) -> Option<usize> {
+    Some(match token {
+        "R" | "E" => 0,
+        "NE" => 1,
+        "U" | "N" => 2,
+        "NW" => 3,
+        "L" | "W" => 4,
+        "SW" => 5,
+        "D" | "S" => 6,
+        "SE" => 7,
+        _ => return None,
+    })
+}
+
+// A straight move along a path: the step to take, and how many times.
+pub type Move = ((i64, i64), i64);
+
+// The most steps a path can take. Every coordinate and step count along it
+// then fits in 64 bits with room to add or subtract a few of them. Anything
+// summed over many visits or wires is worked out in 128 bits.
+pub const MAX_LENGTH: i64 = 1 << 60;
+
+// Parse a path of comma-separated steps, each a direction and a length. The
+// direction is `U`, `D`, `L` or `R`, a compass point from `N`, `NE`, `E` and
+// so on round to `NW`, or `F` to carry on the way the path is heading (east
+// to start with). `TL` and `TR` turn left or right without moving, by 90
+// degrees or by the given multiple of 45. The path can be at most
+// `MAX_LENGTH` steps long.
+pub fn parse_path(path: &str) -> Result<Vec<Move>, Error> {
+    let mut moves = vec![];
+    let mut facing = 0;
+    let mut length: i64 = 0;
+    let mut column = 1;
+    for step in path.split(',') {
+        let parsed = parse_step(step, &mut facing).and_then(|m| {
+            if let Some((_, len)) = m {
+                length = length
+                    .checked_add(len)
+                    .filter(|total| *total <= MAX_LENGTH)
+                    .ok_or_else(|| {
+                        format_err!(
+                            "Invalid length {:?}: path is longer than {} steps",
+                            step,
+                            MAX_LENGTH
+                        )
+                    })?;
+            }
+            Ok(m)
+        });
+        moves.extend(parsed.map_err(|e| format_err!("{} at column {}", e, column))?);
+        column += step.len() + 1;
+    }
+    Ok(moves)
+}
+
+// The move a step makes, if any, turning to face the way it goes.
+fn parse_step(step: &str, facing: &mut usize) -> Result<Option<Move>, Error> {
+    let split = step
+        .find(|c: char| !c.is_ascii_alphabetic())
+        .unwrap_or(step.len());
+    let (token, number) = step.split_at(split);
+    let parse = |what| -> Result<i64, Error> {
+        let n: i64 = number
+            .parse()
+            .map_err(|e| format_err!("Invalid {} {:?}: {}", what, step, e))?;
+        if n < 0 {
+            bail!("Invalid {} {:?}: must not be negative", what, step);
+        }
+        Ok(n)
+    };
+
+    Ok(match token {
+        "TL" | "TR" => {
+            let angle = if number.is_empty() {
+                90
+            } else {
+                parse("angle")?
+            };
+            if angle % 45 != 0 {
+                bail!("Invalid angle {:?}: must be a multiple of 45", step);
+            }
+            let turn = (angle / 45 % 8) as usize;
+            *facing = if token == "TL" {
+                (*facing + turn) % 8
+            } else {
+                (*facing + 8 - turn) % 8
+            };
+            None
+        }
+        "F" => Some((HEADINGS[*facing], parse("length")?)),
+        _ => match heading(token) {
+            Some(h) => {
+                *facing = h;
+                Some((HEADINGS[h], parse("length")?))
+            }
+            None => bail!("Invalid direction {:?}", step),
+        },
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_path() {
+        let test_data = [
+            (
+                "R8,U5,L5,D3",
+                vec![((1, 0), 8), ((0, 1), 5), ((-1, 0), 5), ((0, -1), 3)],
+            ),
+            (
+                "NE3,SW1,N2,W0",
+                vec![((1, 1), 3), ((-1, -1), 1), ((0, 1), 2), ((-1, 0), 0)],
+            ),
+            (
+                "F2,TL,F3,TR45,F1",
+                vec![((1, 0), 2), ((0, 1), 3), ((1, 1), 1)],
+            ),
+            (
+                "S4,TR,F1,TL180,F1,TR360,F2",
+                vec![((0, -1), 4), ((-1, 0), 1), ((1, 0), 1), ((1, 0), 2)],
+            ),
+        ];
+
+        for (path, expected) in &test_data {
+            assert_eq!(parse_path(path).unwrap(), *expected, "{}", path);
+        }
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        let test_data = [
+            ("R8,X5", "Invalid direction \"X5\" at column 4"),
+            ("R8,NE1,NNE2", "Invalid direction \"NNE2\" at column 8"),
+            ("", "Invalid direction \"\" at column 1"),
+            (
+                "R8,U",
+                "Invalid length \"U\": cannot parse integer from empty string at column 4",
+            ),
+            (
+                "R8,U-5",
+                "Invalid length \"U-5\": must not be negative at column 4",
+            ),
+            (
+                "F1,TL30",
+                "Invalid angle \"TL30\": must be a multiple of 45 at column 4",
+            ),
+            (
+                "R9223372036854775807,R1",
+                "Invalid length \"R9223372036854775807\": path is longer than \
+                 1152921504606846976 steps at column 1",
+            ),
+            (
+                "R1152921504606846975,U1,L1",
+                "Invalid length \"L1\": path is longer than 1152921504606846976 steps \
+                 at column 25",
+            ),
+        ];
+
+        for (path, expected) in &test_data {
+            assert_eq!(parse_path(path).err().unwrap().to_string(), *expected);
+        }
+    }
+}
@@ -0,0 +1,298 @@
+use crate::wire::{best_crossing, crossings, Metric, Point, StepPolicy, Wire};
+use failure::{bail, Error};
+use std::fmt::Write;
+
+const COLOURS: [&str; 6] = [
+    "#e6194b", "#3cb44b", "#4363d8", "#f58231", "#911eb4", "#42d4f4",
+];
+const ANSI_COLOURS: [u8; 6] = [31, 32, 34, 35, 36, 33];
+
+// The largest layout drawn as text, in characters.
+pub const MAX_WIDTH: i64 = 200;
+pub const MAX_HEIGHT: i64 = 100;
+
+// The corners of a box holding the origin and every wire.
+fn bounds(wires: &[Wire]) -> (Point, Point) {
+    let origin = Point { x: 0, y: 0 };
+    let points = wires
+        .iter()
+        .flat_map(|w| &w.segments)
+        .map(|s| s.end)
+        .chain(Some(origin));
+    points.fold((origin, origin), |(lo, hi), p| {
+        (
+            Point {
+                x: lo.x.min(p.x),
+                y: lo.y.min(p.y),
+            },
+            Point {
+                x: hi.x.max(p.x),
+                y: hi.y.max(p.y),
+            },
+        )
+    })
+}
+
+// Every point where two or more of the wires cross, with overlapping
+// stretches summarised as in `crossings`.
+fn intersections(wires: &[Wire], policy: StepPolicy) -> Vec<Point> {
+    let mut points = vec![];
+    for i in 0..wires.len() {
+        for j in i + 1..wires.len() {
+            points.extend(
+                crossings(&[&wires[i], &wires[j]], policy)
+                    .iter()
+                    .map(|c| c.point),
+            );
+        }
+    }
+    points.sort();
+    points.dedup();
+    points
+}
+
+// Draw the wires as SVG, each in its own colour, with the origin as a black
+// dot, every intersection circled and the best point where they all cross
+// ringed in gold. Up is up, so y is flipped.
+pub fn svg(wires: &[Wire], metric: Metric, policy: StepPolicy) -> String {
+    let (lo, hi) = bounds(wires);
+    let extent = (hi.x - lo.x).max(hi.y - lo.y).max(1) as f64;
+    let margin = extent / 20.0;
+    let r = extent / 100.0;
+
+    let mut out = String::new();
+    writeln!(
+        out,
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" viewBox=\"{} {} {} {}\">",
+        lo.x as f64 - margin,
+        -hi.y as f64 - margin,
+        (hi.x - lo.x) as f64 + 2.0 * margin,
+        (hi.y - lo.y) as f64 + 2.0 * margin
+    )
+    .unwrap();
+    for (i, wire) in wires.iter().enumerate() {
+        let points: Vec<String> = Some(Point { x: 0, y: 0 })
+            .into_iter()
+            .chain(wire.segments.iter().map(|s| s.end))
+            .map(|p| format!("{},{}", p.x, -p.y))
+            .collect();
+        writeln!(
+            out,
+            "  <polyline points=\"{}\" fill=\"none\" stroke=\"{}\" stroke-width=\"2\" \
+             vector-effect=\"non-scaling-stroke\"><title>wire {}</title></polyline>",
+            points.join(" "),
+            COLOURS[i % COLOURS.len()],
+            i + 1
+        )
+        .unwrap();
+    }
+    for p in intersections(wires, policy) {
+        writeln!(
+            out,
+            "  <circle cx=\"{}\" cy=\"{}\" r=\"{}\" fill=\"none\" stroke=\"black\" \
+             vector-effect=\"non-scaling-stroke\"><title>({}, {})</title></circle>",
+            p.x, -p.y, r, p.x, p.y
+        )
+        .unwrap();
+    }
+    writeln!(
+        out,
+        "  <circle cx=\"0\" cy=\"0\" r=\"{}\" fill=\"black\"/>",
+        r
+    )
+    .unwrap();
+    if let Some(best) = best_crossing(wires, metric, policy) {
+        writeln!(
+            out,
+            "  <circle cx=\"{}\" cy=\"{}\" r=\"{}\" fill=\"none\" stroke=\"gold\" \
+             stroke-width=\"4\" vector-effect=\"non-scaling-stroke\">\
+             <title>best: {} at {}</title></circle>",
+            best.point.x,
+            -best.point.y,
+            2.0 * r,
+            best.score(metric),
+            best
+        )
+        .unwrap();
+    }
+    out.push_str("</svg>\n");
+    out
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Cell {
+    Empty,
+    Wire(usize, char),
+    Crossing,
+}
+
+// Draw the wires as text in the style of the puzzle: `-`, `|`, `/` and `\`
+// along each wire, `+` where it turns, `X` where wires cross, `o` for the
+// origin and `*` for the best point where they all cross. With colour, each
+// wire gets its own ANSI colour.
+pub fn ascii(
+    wires: &[Wire],
+    metric: Metric,
+    policy: StepPolicy,
+    colour: bool,
+) -> Result<String, Error> {
+    let (lo, hi) = bounds(wires);
+    let (width, height) = (hi.x - lo.x + 1, hi.y - lo.y + 1);
+    if width > MAX_WIDTH || height > MAX_HEIGHT {
+        bail!(
+            "Layout is {}x{}, too big to draw as text (at most {}x{})",
+            width,
+            height,
+            MAX_WIDTH,
+            MAX_HEIGHT
+        );
+    }
+
+    let mut grid = vec![vec![Cell::Empty; width as usize]; height as usize];
+    let at = |p: Point| ((hi.y - p.y) as usize, (p.x - lo.x) as usize);
+    for (i, wire) in wires.iter().enumerate() {
+        for (n, s) in wire.segments.iter().enumerate() {
+            let (dx, dy) = (
+                (s.end.x - s.start.x).signum(),
+                (s.end.y - s.start.y).signum(),
+            );
+            let len = (s.end.x - s.start.x).abs().max((s.end.y - s.start.y).abs());
+            for k in 0..=len {
+                let p = Point {
+                    x: s.start.x + dx * k,
+                    y: s.start.y + dy * k,
+                };
+                let c = if k == 0 && n > 0 {
+                    '+'
+                } else if dy == 0 {
+                    '-'
+                } else if dx == 0 {
+                    '|'
+                } else if dx == dy {
+                    '/'
+                } else {
+                    '\\'
+                };
+                let (row, col) = at(p);
+                grid[row][col] = match grid[row][col] {
+                    Cell::Wire(j, _) if j != i => Cell::Crossing,
+                    Cell::Wire(_, old) if old != c => Cell::Wire(i, '+'),
+                    Cell::Crossing => Cell::Crossing,
+                    _ => Cell::Wire(i, c),
+                };
+            }
+        }
+    }
+
+    let best = best_crossing(wires, metric, policy).map(|c| c.point);
+    let mut out = String::new();
+    for (row, cells) in grid.iter().enumerate() {
+        for (col, cell) in cells.iter().enumerate() {
+            let p = Point {
+                x: lo.x + col as i64,
+                y: hi.y - row as i64,
+            };
+            let (c, code) = if Some(p) == best {
+                ('*', Some(1))
+            } else if p == (Point { x: 0, y: 0 }) {
+                ('o', Some(1))
+            } else {
+                match cell {
+                    Cell::Empty => ('.', None),
+                    Cell::Wire(i, c) => (*c, Some(ANSI_COLOURS[i % ANSI_COLOURS.len()])),
+                    Cell::Crossing => ('X', Some(1)),
+                }
+            };
+            match code {
+                Some(code) if colour => write!(out, "\x1b[{}m{}\x1b[0m", code, c).unwrap(),
+                _ => out.push(c),
+            }
+        }
+        out.push('\n');
+    }
+    Ok(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn wires(paths: &[&str]) -> Vec<Wire> {
+        paths.iter().map(|p| Wire::parse(p).unwrap()).collect()
+    }
+
+    #[test]
+    fn test_ascii() {
+        let example = wires(&["R8,U5,L5,D3", "U7,R6,D4,L4"]);
+        let test_data = [
+            (
+                Metric::Manhattan,
+                "+-----+..\n\
+                 |.....|..\n\
+                 |..+--X-+\n\
+                 |..|..|.|\n\
+                 |.-*--+.|\n\
+                 |..|....|\n\
+                 |.......|\n\
+                 o-------+\n",
+            ),
+            (
+                Metric::CombinedSteps,
+                "+-----+..\n\
+                 |.....|..\n\
+                 |..+--*-+\n\
+                 |..|..|.|\n\
+                 |.-X--+.|\n\
+                 |..|....|\n\
+                 |.......|\n\
+                 o-------+\n",
+            ),
+        ];
+
+        for (metric, expected) in &test_data {
+            assert_eq!(
+                ascii(&example, *metric, StepPolicy::FirstVisit, false).unwrap(),
+                *expected
+            );
+        }
+    }
+
+    #[test]
+    fn test_ascii_corners_and_crossings() {
+        let layout = wires(&["L2,U2,R4,D4,L1", "L1,U1,R3"]);
+        assert_eq!(
+            ascii(&layout, Metric::Manhattan, StepPolicy::FirstVisit, false).unwrap(),
+            "+---+\n\
+             |+--X\n\
+             +*o.|\n\
+             ....|\n\
+             ...-+\n"
+        );
+    }
+
+    #[test]
+    fn test_ascii_too_big() {
+        let layout = wires(&["R75,D30,R83,U83,L12,D49,R71,U7,L72", "U62,R66,U55,R34"]);
+        assert_eq!(
+            ascii(&layout, Metric::Manhattan, StepPolicy::FirstVisit, false)
+                .err()
+                .unwrap()
+                .to_string(),
+            "Layout is 218x148, too big to draw as text (at most 200x100)"
+        );
+    }
+
+    #[test]
+    fn test_svg() {
+        let example = wires(&["R8,U5,L5,D3", "U7,R6,D4,L4"]);
+        let svg = svg(&example, Metric::Manhattan, StepPolicy::FirstVisit);
+        assert!(svg.starts_with(
+            "<svg xmlns=\"http://www.w3.org/2000/svg\" viewBox=\"-0.4 -7.4 8.8 7.8\">"
+        ));
+        assert!(svg.contains("<polyline points=\"0,0 8,0 8,-5 3,-5 3,-2\""));
+        assert!(svg.contains("<polyline points=\"0,0 0,-7 6,-7 6,-3 2,-3\""));
+        assert_eq!(svg.matches("<title>(").count(), 2);
+        assert!(svg.contains("<title>best: 6 at (3, 3) steps [20, 20]</title>"));
+        assert!(svg.ends_with("</svg>\n"));
+    }
+}
@@ -0,0 +1,900 @@
+use crate::path::parse_path;
+pub use common::grid::Point;
+use common::parse::parse_lines;
+use failure::{bail, Error};
+use std::collections::{BTreeMap, HashMap};
+use std::convert::TryFrom;
+use std::fmt;
+use std::str::FromStr;
+
+// The ways a straight line can run across the grid.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+enum Orientation {
+    Horizontal,
+    Vertical,
+    // Up and to the right.
+    Diagonal,
+    // Down and to the right.
+    AntiDiagonal,
+}
+
+const ORIENTATIONS: [Orientation; 4] = [
+    Orientation::Horizontal,
+    Orientation::Vertical,
+    Orientation::Diagonal,
+    Orientation::AntiDiagonal,
+];
+
+impl Orientation {
+    // The coordinate that stays the same along a line running this way.
+    fn fixed(self, p: Point) -> i64 {
+        match self {
+            Orientation::Horizontal => p.y,
+            Orientation::Vertical => p.x,
+            Orientation::Diagonal => p.y - p.x,
+            Orientation::AntiDiagonal => p.y + p.x,
+        }
+    }
+}
+
+type Line = (Orientation, i64);
+
+fn span(a: i64, b: i64) -> (i64, i64) {
+    (a.min(b), a.max(b))
+}
+
+// A straight, non-empty stretch of wire, with the number of steps along the
+// wire to reach its start. Diagonal steps count the same as any other.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Segment {
+    pub start: Point,
+    pub end: Point,
+    pub steps: i64,
+}
+
+impl Segment {
+    fn orientation(&self) -> Orientation {
+        let (dx, dy) = (self.end.x - self.start.x, self.end.y - self.start.y);
+        if dy == 0 {
+            Orientation::Horizontal
+        } else if dx == 0 {
+            Orientation::Vertical
+        } else if dx == dy {
+            Orientation::Diagonal
+        } else {
+            Orientation::AntiDiagonal
+        }
+    }
+
+    // The coordinate that varies along the segment: y if it's vertical, and
+    // x otherwise.
+    fn along(&self, p: Point) -> i64 {
+        match self.orientation() {
+            Orientation::Vertical => p.y,
+            _ => p.x,
+        }
+    }
+
+    fn line(&self) -> Line {
+        let o = self.orientation();
+        (o, o.fixed(self.start))
+    }
+
+    fn range(&self) -> (i64, i64) {
+        span(self.along(self.start), self.along(self.end))
+    }
+
+    // +1 if the wire runs towards increasing coordinates, -1 if not.
+    fn direction(&self) -> i64 {
+        (self.along(self.end) - self.along(self.start)).signum()
+    }
+
+    fn point(&self, along: i64) -> Point {
+        let (o, fixed) = self.line();
+        let (x, y) = match o {
+            Orientation::Horizontal => (along, fixed),
+            Orientation::Vertical => (fixed, along),
+            Orientation::Diagonal => (along, fixed + along),
+            Orientation::AntiDiagonal => (along, fixed - along),
+        };
+        Point { x, y }
+    }
+
+    // Where along the segment's line it meets another line, rounded down, or
+    // None if they're parallel.
+    fn along_where(&self, (o, fixed): Line) -> Option<i64> {
+        let base = i128::from(o.fixed(self.point(0)));
+        let slope = i128::from(o.fixed(self.point(1))) - base;
+        meet((slope, base), (0, i128::from(fixed)))
+    }
+
+    // Where the segment crosses one that isn't parallel to it, if they meet
+    // at a point on the grid.
+    fn crossing(&self, other: &Segment) -> Option<Point> {
+        let p = self.point(self.along_where(other.line())?);
+        let (o, fixed) = other.line();
+        if o.fixed(p) == fixed && self.covers(p) && other.covers(p) {
+            Some(p)
+        } else {
+            None
+        }
+    }
+
+    // Steps along the wire to reach a point on this segment.
+    fn steps_to(&self, p: Point) -> i64 {
+        self.steps + (self.along(p) - self.along(self.start)).abs()
+    }
+
+    fn covers(&self, p: Point) -> bool {
+        let (lo, hi) = self.range();
+        lo <= self.along(p) && self.along(p) <= hi
+    }
+
+    // Steps to each point of the segment's line, as a slope and intercept.
+    fn linear(&self) -> Linear {
+        let d = i128::from(self.direction());
+        (
+            d,
+            i128::from(self.steps) - d * i128::from(self.along(self.start)),
+        )
+    }
+}
+
+// Wide enough for the steps over every visit added up.
+type Linear = (i128, i128);
+
+// Where two linear functions meet, rounded down, or None if they're parallel
+// or meet beyond any path.
+fn meet(a: Linear, b: Linear) -> Option<i64> {
+    let (mut n, mut d) = (b.1 - a.1, a.0 - b.0);
+    if d == 0 {
+        return None;
+    }
+    if d < 0 {
+        n = -n;
+        d = -d;
+    }
+    i64::try_from(n.div_euclid(d)).ok()
+}
+
+// Which visits count when a wire passes through a point more than once.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StepPolicy {
+    // The steps to get there the first time, as in the puzzle.
+    FirstVisit,
+    // The steps to get there the last time.
+    LastVisit,
+    // The steps to get there, added up over every visit.
+    EveryVisit,
+}
+
+impl StepPolicy {
+    // Combine the steps at each visit, in order.
+    fn steps(self, visits: &[i64]) -> Option<u128> {
+        match self {
+            StepPolicy::FirstVisit => visits.first().cloned().map(wide),
+            StepPolicy::LastVisit => visits.last().cloned().map(wide),
+            StepPolicy::EveryVisit if visits.is_empty() => None,
+            StepPolicy::EveryVisit => Some(visits.iter().cloned().map(wide).sum()),
+        }
+    }
+}
+
+impl FromStr for StepPolicy {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<StepPolicy, Error> {
+        match s {
+            "first" => Ok(StepPolicy::FirstVisit),
+            "last" => Ok(StepPolicy::LastVisit),
+            "every" => Ok(StepPolicy::EveryVisit),
+            _ => bail!("Invalid step policy {:?}: expected first, last or every", s),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Wire {
+    pub segments: Vec<Segment>,
+    // Indices of the segments along each line.
+    lines: HashMap<Line, Vec<usize>>,
+}
+
+// Parse one wire per line, as in the puzzle input.
+pub fn parse_layout(layout: &str) -> Result<Vec<Wire>, Error> {
+    parse_lines(layout, Wire::parse)
+}
+
+impl Wire {
+    // `parse_path` limits the length of the path, so every coordinate and
+    // step count here fits in 64 bits.
+    pub fn parse(path: &str) -> Result<Wire, Error> {
+        let mut at = Point { x: 0, y: 0 };
+        let mut steps = 0;
+        let mut segments = vec![];
+        for (dir, len) in parse_path(path)? {
+            if len == 0 {
+                continue;
+            }
+            let end = Point {
+                x: at.x + dir.0 * len,
+                y: at.y + dir.1 * len,
+            };
+            segments.push(Segment {
+                start: at,
+                end,
+                steps,
+            });
+            at = end;
+            steps += len;
+        }
+        Ok(Wire::new(segments))
+    }
+
+    pub fn new(segments: Vec<Segment>) -> Wire {
+        let mut lines: HashMap<_, Vec<usize>> = HashMap::new();
+        for (i, s) in segments.iter().enumerate() {
+            lines.entry(s.line()).or_default().push(i);
+        }
+        Wire { segments, lines }
+    }
+
+    fn on_line(&self, line: Line) -> impl Iterator<Item = &Segment> {
+        self.lines
+            .get(&line)
+            .into_iter()
+            .flatten()
+            .map(move |&i| &self.segments[i])
+    }
+
+    // The steps along the wire at each visit to a point, in order.
+    pub fn visits(&self, p: Point) -> Vec<i64> {
+        let mut visits: Vec<_> = ORIENTATIONS
+            .iter()
+            .flat_map(|&o| self.on_line((o, o.fixed(p))))
+            .filter(|s| s.covers(p))
+            .map(|s| s.steps_to(p))
+            .collect();
+        // Where one segment ends and the next starts is a single visit.
+        visits.sort();
+        visits.dedup();
+        visits
+    }
+
+    // The steps along the wire to reach a point under a policy, or None if it
+    // never does. The wire's start doesn't count unless it comes back to it.
+    pub fn steps_to(&self, p: Point, policy: StepPolicy) -> Option<u128> {
+        let visits: Vec<_> = self.visits(p).into_iter().filter(|&s| s > 0).collect();
+        policy.steps(&visits)
+    }
+
+    // Everywhere the wire crosses or runs back along itself, in order of
+    // where they start.
+    pub fn self_crossings(&self) -> Vec<SelfCrossing> {
+        let mut points = vec![];
+        let mut found = vec![];
+        for (i, &oa) in ORIENTATIONS.iter().enumerate() {
+            for &ob in &ORIENTATIONS[i + 1..] {
+                perpendicular_points(self, oa, self, ob, &mut points);
+            }
+        }
+        for (i, sa) in self.segments.iter().enumerate() {
+            let (a1, a2) = sa.range();
+            for &j in &self.lines[&sa.line()] {
+                let sb = &self.segments[j];
+                let (b1, b2) = sb.range();
+                let (lo, hi) = (a1.max(b1), a2.min(b2));
+                if j <= i || lo > hi {
+                    continue;
+                }
+                if lo == hi {
+                    points.push(sa.point(lo));
+                } else {
+                    let start = sa.point(lo);
+                    found.push(SelfCrossing {
+                        start,
+                        end: sa.point(hi),
+                        steps: vec![sa.steps_to(start), sb.steps_to(start)],
+                    });
+                }
+            }
+        }
+        // Points where the wire turns look like crossings, but are only
+        // visited once.
+        found.extend(points.into_iter().filter_map(|p| {
+            let steps = self.visits(p);
+            if steps.len() > 1 {
+                Some(SelfCrossing {
+                    start: p,
+                    end: p,
+                    steps,
+                })
+            } else {
+                None
+            }
+        }));
+        found.sort_by_key(|c| (c.start, c.end));
+        found.dedup();
+        found
+    }
+}
+
+// A point where a wire crosses itself, or a stretch it runs back along, with
+// the steps to reach `start` on each visit.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SelfCrossing {
+    pub start: Point,
+    pub end: Point,
+    pub steps: Vec<i64>,
+}
+
+impl fmt::Display for SelfCrossing {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "({}, {})", self.start.x, self.start.y)?;
+        if self.end != self.start {
+            write!(f, " to ({}, {})", self.end.x, self.end.y)?;
+        }
+        write!(f, " steps {:?}", self.steps)
+    }
+}
+
+// Points where segments of one wire running one way cross segments of
+// another wire running another way. Each way has a coordinate that's fixed
+// along it, and taking the second way's as x and the first's as y turns the
+// first wire's segments horizontal and the second's vertical. Then sweeping
+// left to right over the horizontal segments spanning each vertical one
+// finds where they meet, though diagonals can meet between grid points.
+fn perpendicular_points(
+    a: &Wire,
+    oa: Orientation,
+    b: &Wire,
+    ob: Orientation,
+    points: &mut Vec<Point>,
+) {
+    const ADD: u8 = 0;
+    const QUERY: u8 = 1;
+    const REMOVE: u8 = 2;
+
+    let mut events = vec![];
+    for (i, s) in a.segments.iter().enumerate() {
+        if s.orientation() == oa {
+            let (x1, x2) = span(ob.fixed(s.start), ob.fixed(s.end));
+            events.push((x1, ADD, i));
+            events.push((x2, REMOVE, i));
+        }
+    }
+    for (i, s) in b.segments.iter().enumerate() {
+        if s.orientation() == ob {
+            events.push((ob.fixed(s.start), QUERY, i));
+        }
+    }
+    // Segments that just touch still meet, so add before querying and
+    // remove after.
+    events.sort();
+
+    // The number of horizontal segments spanning the sweep on each row.
+    let mut active: BTreeMap<i64, usize> = BTreeMap::new();
+    for (_, kind, i) in events {
+        match kind {
+            ADD => *active.entry(oa.fixed(a.segments[i].start)).or_default() += 1,
+            REMOVE => {
+                let y = oa.fixed(a.segments[i].start);
+                let count = active.get_mut(&y).unwrap();
+                *count -= 1;
+                if *count == 0 {
+                    active.remove(&y);
+                }
+            }
+            _ => {
+                let s = &b.segments[i];
+                let (y1, y2) = span(oa.fixed(s.start), oa.fixed(s.end));
+                for (&y, _) in active.range(y1..=y2) {
+                    let p = s.point(s.along_where((oa, y)).unwrap());
+                    if oa.fixed(p) == y {
+                        points.push(p);
+                    }
+                }
+            }
+        }
+    }
+}
+
+// Segments of two wires running along the same line and overlapping. Every
+// point of the overlap is shared, but each wire's steps along it are made of
+// linear pieces, which change where any wire's segments on the line start or
+// end, where another of its segments crosses the line, and where two
+// segments' steps meet. Every metric is best at the end of a piece, or where
+// it crosses a line through the origin, so only those points and their
+// neighbours (in case one is the origin, a crossing visit, or the best point
+// is between two on the grid) are reported.
+fn overlapping_points(
+    a: &Wire,
+    b: &Wire,
+    wires: &[&Wire],
+    policy: StepPolicy,
+    points: &mut Vec<Point>,
+) {
+    for sa in &a.segments {
+        let (a1, a2) = sa.range();
+        for sb in b.on_line(sa.line()) {
+            let (b1, b2) = sb.range();
+            let (lo, hi) = (a1.max(b1), a2.min(b2));
+            if lo > hi {
+                continue;
+            }
+            let on_line: Vec<Vec<&Segment>> = wires
+                .iter()
+                .map(|w| w.on_line(sa.line()).collect())
+                .collect();
+            let mut ends = vec![lo, hi];
+            for s in on_line.iter().flatten() {
+                let (s1, s2) = s.range();
+                ends.extend([s1, s2].iter().filter(|&&x| lo <= x && x <= hi));
+            }
+            for s in wires.iter().flat_map(|w| &w.segments) {
+                if let Some(p) = sa.crossing(s) {
+                    ends.extend(Some(sa.along(p)).filter(|&x| lo <= x && x <= hi));
+                }
+            }
+            ends.sort();
+            ends.dedup();
+
+            let mut critical = ends.clone();
+            critical.extend(ORIENTATIONS.iter().filter_map(|&o| sa.along_where((o, 0))));
+            for piece in ends.windows(2) {
+                let mut lines = vec![];
+                for segments in &on_line {
+                    let covering = segments.iter().filter(|s| {
+                        let (s1, s2) = s.range();
+                        s1 <= piece[0] && piece[1] <= s2
+                    });
+                    match policy {
+                        StepPolicy::EveryVisit => lines.push(
+                            covering
+                                .map(|s| s.linear())
+                                .fold((0, 0), |(m, c), (dm, dc)| (m + dm, c + dc)),
+                        ),
+                        _ => lines.extend(covering.map(|s| s.linear())),
+                    }
+                }
+                for (i, &l1) in lines.iter().enumerate() {
+                    critical.extend(lines[i + 1..].iter().filter_map(|&l2| meet(l1, l2)));
+                }
+            }
+            for along in critical {
+                for along in along - 1..=along + 1 {
+                    if lo <= along && along <= hi {
+                        points.push(sa.point(along));
+                    }
+                }
+            }
+        }
+    }
+}
+
+// How to choose the best crossing.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Metric {
+    // Distance from the origin.
+    Manhattan,
+    // Distance from the origin, moving diagonally as easily as straight.
+    Chebyshev,
+    // Straight-line distance from the origin, squared to keep it exact.
+    Euclidean,
+    // Total steps along every wire.
+    CombinedSteps,
+    // Steps along the wire that takes longest to get there.
+    MaxSteps,
+}
+
+impl FromStr for Metric {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Metric, Error> {
+        match s {
+            "manhattan" => Ok(Metric::Manhattan),
+            "chebyshev" => Ok(Metric::Chebyshev),
+            "euclidean" => Ok(Metric::Euclidean),
+            "steps" => Ok(Metric::CombinedSteps),
+            "max-steps" => Ok(Metric::MaxSteps),
+            _ => bail!(
+                "Invalid metric {:?}: expected manhattan, chebyshev, euclidean, steps or max-steps",
+                s
+            ),
+        }
+    }
+}
+
+// A point that wires all pass through, with the steps along each wire to
+// reach it.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Crossing {
+    pub point: Point,
+    pub steps: Vec<u128>,
+}
+
+// Scores and steps are never negative, and are 128 bits wide so that squared
+// distances, and steps added up over many visits or wires, don't overflow.
+fn wide(v: i64) -> u128 {
+    u128::from(v.unsigned_abs())
+}
+
+impl Crossing {
+    pub fn score(&self, metric: Metric) -> u128 {
+        match metric {
+            Metric::Manhattan => wide(self.point.manhattan()),
+            Metric::Chebyshev => wide(self.point.chebyshev()),
+            Metric::Euclidean => self.point.euclidean_squared(),
+            Metric::CombinedSteps => self.steps.iter().sum(),
+            Metric::MaxSteps => self.steps.iter().cloned().max().unwrap_or(0),
+        }
+    }
+}
+
+impl fmt::Display for Crossing {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "({}, {}) steps {:?}",
+            self.point.x, self.point.y, self.steps
+        )
+    }
+}
+
+// The points where all the wires cross, with the steps along each wire
+// under a policy. Overlapping stretches are summarised by the points where a
+// metric could be best, as above.
+pub fn crossings(wires: &[&Wire], policy: StepPolicy) -> Vec<Crossing> {
+    let mut points = vec![];
+    for (i, a) in wires.iter().enumerate() {
+        for b in &wires[i + 1..] {
+            for &oa in &ORIENTATIONS {
+                for &ob in ORIENTATIONS.iter().filter(|&&ob| ob != oa) {
+                    perpendicular_points(a, oa, b, ob, &mut points);
+                }
+            }
+            overlapping_points(a, b, wires, policy, &mut points);
+        }
+    }
+    points.sort();
+    points.dedup();
+    points
+        .into_iter()
+        .filter_map(|point| {
+            let steps = wires
+                .iter()
+                .map(|w| w.steps_to(point, policy))
+                .collect::<Option<Vec<_>>>()?;
+            Some(Crossing { point, steps })
+        })
+        .collect()
+}
+
+fn best(crossings: Vec<Crossing>, metric: Metric) -> Option<Crossing> {
+    crossings.into_iter().min_by_key(|c| c.score(metric))
+}
+
+// The best point where every wire crosses, or None if there isn't one.
+pub fn best_crossing(wires: &[Wire], metric: Metric, policy: StepPolicy) -> Option<Crossing> {
+    if wires.len() < 2 {
+        return None;
+    }
+    best(crossings(&wires.iter().collect::<Vec<_>>(), policy), metric)
+}
+
+// The best crossing for each pair of wires.
+pub fn pairwise(
+    wires: &[Wire],
+    metric: Metric,
+    policy: StepPolicy,
+) -> Vec<((usize, usize), Option<Crossing>)> {
+    let mut pairs = vec![];
+    for i in 0..wires.len() {
+        for j in i + 1..wires.len() {
+            let crossing = best(crossings(&[&wires[i], &wires[j]], policy), metric);
+            pairs.push(((i, j), crossing));
+        }
+    }
+    pairs
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use common::random::Random;
+
+    // Every point a wire visits, with the steps at each visit, much as the
+    // wires were originally solved.
+    fn points(path: &str) -> HashMap<Point, Vec<i64>> {
+        let mut points = HashMap::new();
+        let mut at = Point { x: 0, y: 0 };
+        let mut steps = 0;
+        for (dir, len) in parse_path(path).unwrap() {
+            for _ in 0..len {
+                at = Point {
+                    x: at.x + dir.0,
+                    y: at.y + dir.1,
+                };
+                steps += 1;
+                points.entry(at).or_insert_with(Vec::new).push(steps);
+            }
+        }
+        points
+    }
+
+    fn brute_force(paths: &[&str], metric: Metric, policy: StepPolicy) -> Option<u128> {
+        let wires: Vec<_> = paths.iter().map(|p| points(p)).collect();
+        wires[0]
+            .keys()
+            .filter_map(|p| {
+                let steps = wires
+                    .iter()
+                    .map(|w| policy.steps(w.get(p)?))
+                    .collect::<Option<Vec<_>>>()?;
+                Some(Crossing { point: *p, steps }.score(metric))
+            })
+            .min()
+    }
+
+    fn solve(paths: &[&str], metric: Metric, policy: StepPolicy) -> Option<u128> {
+        let wires: Vec<_> = paths.iter().map(|p| Wire::parse(p).unwrap()).collect();
+        best_crossing(&wires, metric, policy).map(|c| c.score(metric))
+    }
+
+    const METRICS: [Metric; 5] = [
+        Metric::Manhattan,
+        Metric::CombinedSteps,
+        Metric::MaxSteps,
+        Metric::Chebyshev,
+        Metric::Euclidean,
+    ];
+    const POLICIES: [StepPolicy; 3] = [
+        StepPolicy::FirstVisit,
+        StepPolicy::LastVisit,
+        StepPolicy::EveryVisit,
+    ];
+
+    // Compare random layouts of 2 to 4 wires against the brute force, with
+    // steps made from the given directions.
+    fn check_random_layouts(directions: &[&str]) {
+        let mut random = Random::new(2019);
+        for num_wires in 2..=4 {
+            for _ in 0..200 {
+                let paths: Vec<String> = (0..num_wires)
+                    .map(|_| {
+                        (0..20)
+                            .map(|_| {
+                                match directions[random.below(directions.len() as u64) as usize] {
+                                    turn if turn.starts_with('T') => turn.to_string(),
+                                    dir => format!("{}{}", dir, random.below(8)),
+                                }
+                            })
+                            .collect::<Vec<_>>()
+                            .join(",")
+                    })
+                    .collect();
+                let paths: Vec<&str> = paths.iter().map(String::as_str).collect();
+                for metric in &METRICS {
+                    for policy in &POLICIES {
+                        assert_eq!(
+                            solve(&paths, *metric, *policy),
+                            brute_force(&paths, *metric, *policy),
+                            "{:?} {:?} of {:?}",
+                            metric,
+                            policy,
+                            paths
+                        );
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_matches_brute_force() {
+        check_random_layouts(&["L", "R", "U", "D"]);
+    }
+
+    #[test]
+    fn test_diagonals_match_brute_force() {
+        check_random_layouts(&[
+            "L", "R", "U", "D", "NE", "NW", "SE", "SW", "F", "TL", "TR45",
+        ]);
+    }
+
+    #[test]
+    fn test_diagonals() {
+        let test_data = [
+            // Diagonals crossing between grid points don't meet.
+            (["NE4", "E1,NW4"], [None, None, None, None, None]),
+            (
+                ["NE4", "E2,NW4"],
+                [Some(2), Some(4), Some(3), Some(1), Some(2)],
+            ),
+            (
+                ["E3,N3", "NE5"],
+                [Some(6), Some(9), Some(6), Some(3), Some(18)],
+            ),
+            (
+                ["N2,E2", "F1,TL,F1,TL45,F3"],
+                [Some(2), Some(5), Some(3), Some(2), Some(4)],
+            ),
+        ];
+
+        for (paths, expected) in &test_data {
+            for (metric, expected) in METRICS.iter().zip(expected) {
+                let policy = StepPolicy::FirstVisit;
+                assert_eq!(
+                    solve(paths, *metric, policy),
+                    *expected,
+                    "{:?} of {:?}",
+                    metric,
+                    paths
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_overlaps() {
+        let test_data = [
+            (["R10", "R5"], [Some(1), Some(2), Some(1)]),
+            (["L3,R10", "U2,D2,R5"], [Some(0), Some(10), Some(6)]),
+            (["R2,U2,L2,D2", "U1,R4"], [Some(1), Some(6), Some(3)]),
+            (["R10", "U1,R10,D1,L10"], [Some(1), Some(22), Some(12)]),
+            (["R5", "U1,R5"], [None, None, None]),
+        ];
+
+        for (paths, expected) in &test_data {
+            for (metric, expected) in METRICS.iter().zip(expected) {
+                let policy = StepPolicy::FirstVisit;
+                assert_eq!(
+                    solve(paths, *metric, policy),
+                    *expected,
+                    "{:?} of {:?}",
+                    metric,
+                    paths
+                );
+                assert_eq!(
+                    solve(paths, *metric, policy),
+                    brute_force(paths, *metric, policy)
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_pairwise() {
+        let wires: Vec<Wire> = ["R8,U5,L5,D3", "U7,R6,D4,L4", "L1,U9"]
+            .iter()
+            .map(|p| Wire::parse(p).unwrap())
+            .collect();
+        let pairs = pairwise(&wires, Metric::Manhattan, StepPolicy::FirstVisit);
+        let scores: Vec<_> = pairs
+            .iter()
+            .map(|(pair, c)| (*pair, c.as_ref().map(|c| c.score(Metric::Manhattan))))
+            .collect();
+        assert_eq!(
+            scores,
+            vec![((0, 1), Some(6)), ((0, 2), None), ((1, 2), None)]
+        );
+        for w in &[&wires[..], &wires[..1]] {
+            assert_eq!(
+                best_crossing(w, Metric::Manhattan, StepPolicy::FirstVisit),
+                None
+            );
+        }
+    }
+
+    #[test]
+    fn test_revisits() {
+        // The first wire loops back through (2, 0) at steps 2 and 10, so
+        // (2, 1) is best unless only the first visit counts.
+        let wires: Vec<_> = ["R4,U2,L2,D4", "U1,R2,D1"]
+            .iter()
+            .map(|p| Wire::parse(p).unwrap())
+            .collect();
+        let loop_point = Point { x: 2, y: 0 };
+        let test_data = [
+            (StepPolicy::FirstVisit, 2, (loop_point, vec![2, 4])),
+            (
+                StepPolicy::LastVisit,
+                10,
+                (Point { x: 2, y: 1 }, vec![9, 3]),
+            ),
+            (
+                StepPolicy::EveryVisit,
+                12,
+                (Point { x: 2, y: 1 }, vec![9, 3]),
+            ),
+        ];
+
+        assert_eq!(wires[0].visits(loop_point), vec![2, 10]);
+        for (policy, steps, (point, best_steps)) in &test_data {
+            assert_eq!(wires[0].steps_to(loop_point, *policy), Some(*steps));
+            let best = best_crossing(&wires, Metric::CombinedSteps, *policy).unwrap();
+            assert_eq!((best.point, best.steps), (*point, best_steps.clone()));
+        }
+    }
+
+    #[test]
+    fn test_many_long_visits() {
+        // Twenty-one visits to (2^59, 0), each over 2^59 steps in.
+        let far = 1 << 59;
+        let path = format!("R{}{}", far, ",R1,L1".repeat(20));
+        let wire = Wire::parse(&path).unwrap();
+        let point = Point { x: far, y: 0 };
+        let every = 21 * (far as u128) + 420;
+        assert_eq!(wire.steps_to(point, StepPolicy::EveryVisit), Some(every));
+
+        let across = Wire::parse(&format!("U1,R{},D2", far)).unwrap();
+        let best = best_crossing(
+            &[wire.clone(), across],
+            Metric::CombinedSteps,
+            StepPolicy::EveryVisit,
+        )
+        .unwrap();
+        assert_eq!(
+            (best.point, best.steps),
+            (point, vec![every, far as u128 + 2])
+        );
+
+        let along = Wire::parse(&format!("R{}", far + 1)).unwrap();
+        let best = best_crossing(
+            &[wire, along],
+            Metric::CombinedSteps,
+            StepPolicy::EveryVisit,
+        )
+        .unwrap();
+        assert_eq!((best.point, best.steps), (Point { x: 1, y: 0 }, vec![1, 1]));
+    }
+
+    #[test]
+    fn test_self_crossings() {
+        let test_data = [
+            ("R8,U5,L5,D3", vec![]),
+            ("R4,U2,L2,D4", vec!["(2, 0) steps [2, 10]"]),
+            ("R2,U2,L2,D2", vec!["(0, 0) steps [0, 8]"]),
+            (
+                "R5,L2,U1,D3",
+                vec![
+                    "(3, 0) steps [3, 7, 9]",
+                    "(3, 0) to (3, 1) steps [7, 9]",
+                    "(3, 0) to (5, 0) steps [3, 7]",
+                ],
+            ),
+            (
+                "U3,D3,R1,L1",
+                vec![
+                    "(0, 0) steps [0, 6, 8]",
+                    "(0, 0) to (0, 3) steps [0, 6]",
+                    "(0, 0) to (1, 0) steps [6, 8]",
+                ],
+            ),
+        ];
+
+        for (path, expected) in &test_data {
+            let crossings: Vec<_> = Wire::parse(path)
+                .unwrap()
+                .self_crossings()
+                .iter()
+                .map(|c| c.to_string())
+                .collect();
+            assert_eq!(crossings, *expected, "{}", path);
+        }
+    }
+
+    #[test]
+    fn test_metric() {
+        assert_eq!("max-steps".parse::<Metric>().unwrap(), Metric::MaxSteps);
+        assert_eq!(
+            "hamming".parse::<Metric>().err().unwrap().to_string(),
+            "Invalid metric \"hamming\": expected manhattan, chebyshev, euclidean, steps or max-steps"
+        );
+        assert_eq!("last".parse::<StepPolicy>().unwrap(), StepPolicy::LastVisit);
+        assert_eq!(
+            "all".parse::<StepPolicy>().err().unwrap().to_string(),
+            "Invalid step policy \"all\": expected first, last or every"
+        );
+    }
+}
@@ -0,0 +1,77 @@
+use advent2019_day4::count::{count_non_decreasing, parse_digits};
+use advent2019_day4::rules::{count_matching, parse_rule_sets};
+use advent2019_day4::PUZZLE_RULES;
+use failure::{bail, format_err, Error};
+use itertools::Itertools;
+use std::env;
+use std::fs;
+
+const USAGE: &str = "usage: passwords [--rules FILE] [--base B] START-END [NAME]...";
+
+fn to_u64(digits: &[u32], base: u32) -> Option<u64> {
+    digits.iter().try_fold(0u64, |n, &d| {
+        n.checked_mul(u64::from(base))?.checked_add(u64::from(d))
+    })
+}
+
+// Count the numbers in a range matching each named rule set from a rules
+// file, or the puzzle's rules by default. Rules that require digits never to
+// decrease are counted without listing the numbers, so work for any range;
+// others check each number in turn.
+fn main() -> Result<(), Error> {
+    let mut rules_path = None;
+    let mut base = 10;
+    let mut positional = vec![];
+
+    let mut args = env::args().skip(1);
+    while let Some(arg) = args.next() {
+        let mut value = || args.next().ok_or_else(|| format_err!("{}", USAGE));
+        match arg.as_str() {
+            "--rules" => rules_path = Some(value()?),
+            "--base" => base = value()?.parse()?,
+            _ if arg.starts_with("--") => bail!("{}", USAGE),
+            _ => positional.push(arg),
+        }
+    }
+    if positional.is_empty() {
+        bail!("{}", USAGE);
+    }
+    let (lo, hi) = positional[0]
+        .split('-')
+        .collect_tuple()
+        .ok_or_else(|| format_err!("Expected START-END, got {:?}", positional[0]))?;
+    let (lo, hi) = (parse_digits(lo, base)?, parse_digits(hi, base)?);
+    let names = &positional[1..];
+
+    let config = match rules_path {
+        Some(path) => fs::read_to_string(path)?,
+        None => PUZZLE_RULES.to_string(),
+    };
+    let rule_sets = parse_rule_sets(&config)?;
+    for name in names {
+        if !rule_sets.iter().any(|(n, _)| n == name) {
+            let known: Vec<&str> = rule_sets.iter().map(|(n, _)| n.as_str()).collect();
+            bail!(
+                "No rule set {:?}, expected one of: {}",
+                name,
+                known.join(", ")
+            );
+        }
+    }
+
+    for (name, rule) in &rule_sets {
+        if !names.is_empty() && !names.contains(name) {
+            continue;
+        }
+        let count = if rule.requires_non_decreasing() {
+            count_non_decreasing(&lo, &hi, base, rule)?.to_string()
+        } else {
+            match (to_u64(&lo, base), to_u64(&hi, base)) {
+                (Some(lo), Some(hi)) => count_matching(&(lo..=hi), rule, base).to_string(),
+                _ => bail!("Range is too big to check {:?} one number at a time", name),
+            }
+        };
+        println!("{}: {}", name, count);
+    }
+    Ok(())
+}
@@ -0,0 +1,370 @@
+use crate::rules::Rule;
+use failure::{bail, format_err, Error};
+use num_bigint::BigUint;
+use num_traits::{One, Zero};
+use std::collections::HashMap;
+
+// Parse a number written in a base, as digits most significant first.
+pub fn parse_digits(number: &str, base: u32) -> Result<Vec<u32>, Error> {
+    if !(2..=36).contains(&base) {
+        bail!("Invalid base {}: must be from 2 to 36", base);
+    }
+    if number.is_empty() {
+        bail!("Expected a number, found nothing");
+    }
+    let digits = number
+        .chars()
+        .map(|c| {
+            c.to_digit(base).ok_or_else(|| {
+                format_err!("Invalid digit {:?} in {:?} for base {}", c, number, base)
+            })
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(strip_leading_zeros(digits))
+}
+
+fn strip_leading_zeros(digits: Vec<u32>) -> Vec<u32> {
+    let zeros = digits.iter().take_while(|&&d| d == 0).count();
+    let zeros = zeros.min(digits.len().saturating_sub(1));
+    digits[zeros..].to_vec()
+}
+
+// Counts the numbers whose digits never decrease and which match a rule,
+// without listing them. When digits never decrease, each digit used makes up
+// a single run, so a rule only depends on how many of each digit there are.
+// Choosing those counts one digit at a time, the only state to carry along
+// is which of the rule's basic parts have held so far.
+struct Counter<'a> {
+    base: u32,
+    rule: &'a Rule,
+    atoms: Vec<&'a Rule>,
+    memo: HashMap<(u32, usize, u64), BigUint>,
+}
+
+fn collect_atoms<'a>(rule: &'a Rule, atoms: &mut Vec<&'a Rule>) {
+    match rule {
+        Rule::NonDecreasing => {}
+        Rule::And(rules) | Rule::Or(rules) => {
+            for r in rules {
+                collect_atoms(r, atoms);
+            }
+        }
+        Rule::Not(r) => collect_atoms(r, atoms),
+        atom => {
+            if !atoms.contains(&atom) {
+                atoms.push(atom);
+            }
+        }
+    }
+}
+
+impl<'a> Counter<'a> {
+    fn new(base: u32, rule: &'a Rule) -> Result<Counter<'a>, Error> {
+        let mut atoms = vec![];
+        collect_atoms(rule, &mut atoms);
+        if atoms.len() > 64 {
+            bail!("Rule has {} parts, at most 64 can be counted", atoms.len());
+        }
+        Ok(Counter {
+            base,
+            rule,
+            atoms,
+            memo: HashMap::new(),
+        })
+    }
+
+    // Which parts hold before any digits are counted: only counts that
+    // allow a digit not to appear at all.
+    fn initial(&self) -> u64 {
+        self.atoms
+            .iter()
+            .enumerate()
+            .filter(|(_, atom)| matches!(atom, Rule::Count { min: 0, .. }))
+            .fold(0, |mask, (i, _)| mask | 1 << i)
+    }
+
+    // Which parts hold once a digit is known to appear `count` times.
+    fn update(&self, mut mask: u64, digit: u32, count: usize) -> u64 {
+        for (i, atom) in self.atoms.iter().enumerate() {
+            match atom {
+                Rule::RunExactly(n) if count > 0 && count == *n => mask |= 1 << i,
+                Rule::RunAtLeast(n) if count > 0 && count >= *n => mask |= 1 << i,
+                Rule::Count { digit: d, min, max } if *d == digit => {
+                    if *min <= count && count <= *max {
+                        mask |= 1 << i;
+                    } else {
+                        mask &= !(1 << i);
+                    }
+                }
+                _ => {}
+            }
+        }
+        mask
+    }
+
+    fn eval(&self, rule: &Rule, mask: u64) -> bool {
+        match rule {
+            Rule::NonDecreasing => true,
+            Rule::And(rules) => rules.iter().all(|r| self.eval(r, mask)),
+            Rule::Or(rules) => rules.iter().any(|r| self.eval(r, mask)),
+            Rule::Not(r) => !self.eval(r, mask),
+            atom => {
+                let i = self.atoms.iter().position(|a| *a == atom).unwrap();
+                mask & 1 << i != 0
+            }
+        }
+    }
+
+    // Ways to fill `remaining` places with digits from `digit` up, in order.
+    fn free(&mut self, digit: u32, remaining: usize, mask: u64) -> BigUint {
+        if digit == self.base {
+            return if remaining == 0 && self.eval(self.rule, mask) {
+                BigUint::one()
+            } else {
+                BigUint::zero()
+            };
+        }
+        if let Some(count) = self.memo.get(&(digit, remaining, mask)) {
+            return count.clone();
+        }
+        let mut count = BigUint::zero();
+        for used in 0..=remaining {
+            let next = self.update(mask, digit, used);
+            count += self.free(digit + 1, remaining - used, next);
+        }
+        self.memo.insert((digit, remaining, mask), count.clone());
+        count
+    }
+
+    // As above, where `digit` already appears `carried` times.
+    fn free_after(&mut self, digit: u32, carried: usize, remaining: usize, mask: u64) -> BigUint {
+        let mut count = BigUint::zero();
+        for used in 0..=remaining {
+            let next = self.update(mask, digit, carried + used);
+            count += self.free(digit + 1, remaining - used, next);
+        }
+        count
+    }
+
+    // Move on from `last`, seen `carried` times, to `digit`, counting every
+    // digit in between as not appearing.
+    fn advance(&self, mut mask: u64, last: u32, carried: usize, digit: u32) -> u64 {
+        if digit != last {
+            mask = self.update(mask, last, carried);
+            for skipped in last + 1..digit {
+                mask = self.update(mask, skipped, 0);
+            }
+        }
+        mask
+    }
+
+    fn matches(&self, digits: &[u32]) -> bool {
+        if !digits.windows(2).all(|w| w[0] <= w[1]) {
+            return false;
+        }
+        let mut mask = self.initial();
+        for digit in 0..self.base {
+            let count = digits.iter().filter(|&&d| d == digit).count();
+            mask = self.update(mask, digit, count);
+        }
+        self.eval(self.rule, mask)
+    }
+
+    // The matching numbers from 0 to n, inclusive. Those with fewer digits
+    // than n can use any digits but 0, and then the rest go digit by digit
+    // below n, keeping to n's digits for as long as they never decrease.
+    fn up_to(&mut self, n: &[u32]) -> BigUint {
+        let mut count = BigUint::zero();
+        if self.matches(&[0]) {
+            count += BigUint::one();
+        }
+        let no_zeros = self.update(self.initial(), 0, 0);
+        for len in 1..n.len() {
+            count += self.free(1, len, no_zeros);
+        }
+
+        let (mut mask, mut last, mut carried) = (self.initial(), 0, 0);
+        for (i, &limit) in n.iter().enumerate() {
+            let lowest = if i == 0 { 1 } else { last };
+            for digit in lowest..limit {
+                let next = self.advance(mask, last, carried, digit);
+                let carried = if digit == last { carried + 1 } else { 1 };
+                count += self.free_after(digit, carried, n.len() - i - 1, next);
+            }
+            if limit < lowest {
+                return count;
+            }
+            mask = self.advance(mask, last, carried, limit);
+            carried = if limit == last { carried + 1 } else { 1 };
+            last = limit;
+        }
+        if self.matches(n) {
+            count += BigUint::one();
+        }
+        count
+    }
+}
+
+// The number of numbers from lo to hi inclusive, given as digits in a base,
+// whose digits never decrease and which match a rule.
+pub fn count_non_decreasing(
+    lo: &[u32],
+    hi: &[u32],
+    base: u32,
+    rule: &Rule,
+) -> Result<BigUint, Error> {
+    if base < 2 {
+        bail!("Invalid base {}: must be at least 2", base);
+    }
+    if let Some(d) = lo.iter().chain(hi).find(|&&d| d >= base) {
+        bail!("Invalid digit {} for base {}", d, base);
+    }
+    let (lo, hi) = (
+        strip_leading_zeros(lo.to_vec()),
+        strip_leading_zeros(hi.to_vec()),
+    );
+    if (lo.len(), &lo) > (hi.len(), &hi) {
+        return Ok(BigUint::zero());
+    }
+    let mut counter = Counter::new(base, rule)?;
+    let mut count = counter.up_to(&hi) - counter.up_to(&lo);
+    if counter.matches(&lo) {
+        count += BigUint::one();
+    }
+    Ok(count)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rules::{count_matching, digits, parse_rule};
+    use common::random::Random;
+
+    fn count(lo: u64, hi: u64, base: u32, rule: &Rule) -> BigUint {
+        count_non_decreasing(&digits(lo, base), &digits(hi, base), base, rule).unwrap()
+    }
+
+    // A random rule made of up to `depth` levels of and, or and not.
+    fn random_rule(random: &mut Random, depth: u32) -> Rule {
+        match random.below(if depth == 0 { 3 } else { 6 }) {
+            0 => Rule::RunExactly(random.below(4) as usize + 1),
+            1 => Rule::RunAtLeast(random.below(4) as usize),
+            2 => {
+                let min = random.below(3) as usize;
+                Rule::Count {
+                    digit: random.below(5) as u32,
+                    min,
+                    max: min + random.below(3) as usize,
+                }
+            }
+            3 => Rule::And((0..2).map(|_| random_rule(random, depth - 1)).collect()),
+            4 => Rule::Or((0..2).map(|_| random_rule(random, depth - 1)).collect()),
+            _ => Rule::Not(Box::new(random_rule(random, depth - 1))),
+        }
+    }
+
+    #[test]
+    fn test_matches_brute_force() {
+        let mut random = Random::new(2019);
+        for _ in 0..500 {
+            let base = random.below(9) as u32 + 2;
+            let rule = random_rule(&mut random, 2);
+            let lo = random.below(3000);
+            let hi = lo + random.below(3000);
+            let brute_force = count_matching(
+                &(lo..=hi),
+                &Rule::And(vec![Rule::NonDecreasing, rule.clone()]),
+                base,
+            );
+            assert_eq!(
+                count(lo, hi, base, &rule),
+                BigUint::from(brute_force),
+                "{:?} from {} to {} in base {}",
+                rule,
+                lo,
+                hi,
+                base
+            );
+        }
+    }
+
+    #[test]
+    fn test_rules_requiring_non_decreasing() {
+        // These can be counted without adding `non-decreasing` on top.
+        let test_data = [
+            "(non-decreasing and run-exactly 2) or (non-decreasing and count 9 2)",
+            "not not non-decreasing and run-at-least 3",
+            "not (not non-decreasing or count 1 ..0)",
+        ];
+
+        for rule in &test_data {
+            let rule = parse_rule(rule).unwrap();
+            assert!(rule.requires_non_decreasing());
+            assert_eq!(
+                count(0, 200_000, 10, &rule),
+                BigUint::from(count_matching(&(0..=200_000), &rule, 10)),
+                "{:?}",
+                rule
+            );
+        }
+    }
+
+    #[test]
+    fn test_long_numbers() {
+        // There are C(n + b - 1, b - 1) ways to choose n digits in order
+        // from b, and counting 0 as the empty choice covers every number
+        // below b^n.
+        let test_data = [
+            ("9".repeat(12), 10, "293930"),
+            ("9".repeat(100), 10, "4263421511271"),
+            ("z".repeat(60), 36, "120141187248715542633439638"),
+        ];
+
+        for (hi, base, expected) in &test_data {
+            let hi = parse_digits(hi, *base).unwrap();
+            assert_eq!(
+                count_non_decreasing(&[0], &hi, *base, &Rule::NonDecreasing)
+                    .unwrap()
+                    .to_string(),
+                *expected
+            );
+        }
+    }
+
+    #[test]
+    fn test_edge_cases() {
+        let pair = Rule::RunExactly(2);
+        assert_eq!(count(0, 0, 10, &Rule::RunAtLeast(1)), BigUint::one());
+        assert_eq!(count(11, 11, 10, &pair), BigUint::one());
+        assert_eq!(count(12, 11, 10, &pair), BigUint::zero());
+        assert_eq!(count(100, 110, 10, &pair), BigUint::zero());
+        assert_eq!(
+            count_non_decreasing(&[0, 0, 1, 1], &[2, 2], 10, &pair).unwrap(),
+            BigUint::from(2u32)
+        );
+        assert_eq!(
+            count_non_decreasing(&[1], &[2, 10], 10, &pair)
+                .err()
+                .unwrap()
+                .to_string(),
+            "Invalid digit 10 for base 10"
+        );
+    }
+
+    #[test]
+    fn test_parse_digits() {
+        assert_eq!(parse_digits("00zA", 36).unwrap(), vec![35, 10]);
+        assert_eq!(parse_digits("000", 2).unwrap(), vec![0]);
+        let test_data = [
+            ("12", 1, "Invalid base 1: must be from 2 to 36"),
+            ("", 10, "Expected a number, found nothing"),
+            ("102", 2, "Invalid digit '2' in \"102\" for base 2"),
+        ];
+        for (number, base, expected) in &test_data {
+            assert_eq!(
+                parse_digits(number, *base).err().unwrap().to_string(),
+                *expected
+            );
+        }
+    }
+}
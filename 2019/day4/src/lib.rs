@@ -0,0 +1,75 @@
+use failure::{format_err, Error};
+use itertools::Itertools;
+use num_traits::ToPrimitive;
+use solution::{Answer, Solution};
+use std::ops::RangeInclusive;
+
+pub mod count;
+pub mod rules;
+
+use count::count_non_decreasing;
+use rules::{digits, parse_rule_sets, Rule};
+
+pub const PUZZLE_RULES: &str = include_str!("../rules.txt");
+
+fn puzzle_rule(name: &str) -> Rule {
+    parse_rule_sets(PUZZLE_RULES)
+        .unwrap()
+        .into_iter()
+        .find(|(n, _)| n == name)
+        .unwrap()
+        .1
+}
+
+fn count_passwords(range: &RangeInclusive<u64>, rule: &str) -> Answer {
+    let (lo, hi) = (digits(*range.start(), 10), digits(*range.end(), 10));
+    let count = count_non_decreasing(&lo, &hi, 10, &puzzle_rule(rule)).unwrap();
+    count.to_i64().unwrap().into()
+}
+
+pub fn parse_range(range: &str) -> Result<RangeInclusive<u64>, Error> {
+    let (start, end) = range
+        .split('-')
+        .collect_tuple()
+        .ok_or_else(|| format_err!("Expected START-END, got {:?}", range))?;
+    Ok(start.parse()?..=end.parse()?)
+}
+
+pub struct Day4;
+
+impl Solution for Day4 {
+    type Input = RangeInclusive<u64>;
+
+    fn parse(input: &str) -> Result<RangeInclusive<u64>, Error> {
+        parse_range(input)
+    }
+
+    fn part1(range: &RangeInclusive<u64>) -> Answer {
+        count_passwords(range, "part1")
+    }
+
+    fn part2(range: &RangeInclusive<u64>) -> Answer {
+        count_passwords(range, "part2")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_puzzle_rules() {
+        let test_data = [
+            ("part1", 111111, true),
+            ("part1", 223450, false),
+            ("part1", 123789, false),
+            ("part2", 112233, true),
+            ("part2", 123444, false),
+            ("part2", 111122, true),
+        ];
+
+        for (name, n, expected) in &test_data {
+            assert_eq!(puzzle_rule(name).matches(&digits(*n, 10)), *expected);
+        }
+    }
+}
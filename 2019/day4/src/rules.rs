@@ -0,0 +1,423 @@
+use failure::{bail, format_err, Error};
+use std::ops::RangeInclusive;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Rule {
+    // Each digit is at least as big as the one before.
+    NonDecreasing,
+    // Some run of equal digits is exactly this long.
+    RunExactly(usize),
+    // Some run of equal digits is at least this long.
+    RunAtLeast(usize),
+    // A digit appears between `min` and `max` times, inclusive.
+    Count { digit: u32, min: usize, max: usize },
+    And(Vec<Rule>),
+    Or(Vec<Rule>),
+    Not(Box<Rule>),
+}
+
+// The digits of a number, most significant first.
+pub fn digits(mut n: u64, base: u32) -> Vec<u32> {
+    let mut digits = vec![];
+    loop {
+        digits.push((n % u64::from(base)) as u32);
+        n /= u64::from(base);
+        if n == 0 {
+            break;
+        }
+    }
+    digits.reverse();
+    digits
+}
+
+fn run_lengths(digits: &[u32]) -> Vec<usize> {
+    let mut runs: Vec<usize> = vec![];
+    for (i, d) in digits.iter().enumerate() {
+        match runs.last_mut() {
+            Some(len) if digits[i - 1] == *d => *len += 1,
+            _ => runs.push(1),
+        }
+    }
+    runs
+}
+
+impl Rule {
+    pub fn matches(&self, digits: &[u32]) -> bool {
+        match self {
+            Rule::NonDecreasing => digits.windows(2).all(|w| w[0] <= w[1]),
+            Rule::RunExactly(n) => run_lengths(digits).contains(n),
+            Rule::RunAtLeast(n) => run_lengths(digits).iter().any(|len| len >= n),
+            Rule::Count { digit, min, max } => {
+                let count = digits.iter().filter(|d| *d == digit).count();
+                *min <= count && count <= *max
+            }
+            Rule::And(rules) => rules.iter().all(|r| r.matches(digits)),
+            Rule::Or(rules) => rules.iter().any(|r| r.matches(digits)),
+            Rule::Not(rule) => !rule.matches(digits),
+        }
+    }
+
+    // Whether only numbers with digits that never decrease can match.
+    pub fn requires_non_decreasing(&self) -> bool {
+        self.implies_non_decreasing(false)
+    }
+
+    // As above, for the rule or, if negated, its opposite. Negating swaps
+    // `and` for `or`, so one part of an `and` is enough to rule out digits
+    // that decrease, but every part of an `or` has to.
+    fn implies_non_decreasing(&self, negated: bool) -> bool {
+        match self {
+            Rule::NonDecreasing => !negated,
+            Rule::And(rules) if !negated => rules.iter().any(|r| r.implies_non_decreasing(false)),
+            Rule::Or(rules) if negated => rules.iter().any(|r| r.implies_non_decreasing(true)),
+            Rule::And(rules) | Rule::Or(rules) => {
+                rules.iter().all(|r| r.implies_non_decreasing(negated))
+            }
+            Rule::Not(rule) => rule.implies_non_decreasing(!negated),
+            _ => false,
+        }
+    }
+}
+
+// The number of numbers in a range that match a rule, written in a base,
+// checking each in turn.
+pub fn count_matching(range: &RangeInclusive<u64>, rule: &Rule, base: u32) -> usize {
+    range
+        .clone()
+        .filter(|n| rule.matches(&digits(*n, base)))
+        .count()
+}
+
+struct Parser<'a> {
+    tokens: Vec<&'a str>,
+    pos: usize,
+}
+
+impl<'a> Parser<'a> {
+    fn next(&mut self) -> Option<&'a str> {
+        let token = self.tokens.get(self.pos).cloned();
+        self.pos += 1;
+        token
+    }
+
+    fn peek(&self) -> Option<&'a str> {
+        self.tokens.get(self.pos).cloned()
+    }
+
+    fn expect(&mut self, what: &str) -> Result<&'a str, Error> {
+        self.next()
+            .ok_or_else(|| format_err!("Expected {}, found end of rule", what))
+    }
+
+    fn number<T: std::str::FromStr>(&mut self, what: &str) -> Result<T, Error> {
+        let token = self.expect(what)?;
+        token
+            .parse()
+            .map_err(|_| format_err!("Expected {}, found {:?}", what, token))
+    }
+
+    // Rules joined by a keyword, such as `a or b or c`.
+    fn joined(
+        &mut self,
+        keyword: &str,
+        join: fn(Vec<Rule>) -> Rule,
+        part: fn(&mut Parser<'a>) -> Result<Rule, Error>,
+    ) -> Result<Rule, Error> {
+        let mut rules = vec![part(self)?];
+        while self.peek() == Some(keyword) {
+            self.next();
+            rules.push(part(self)?);
+        }
+        Ok(if rules.len() == 1 {
+            rules.pop().unwrap()
+        } else {
+            join(rules)
+        })
+    }
+
+    fn or(&mut self) -> Result<Rule, Error> {
+        self.joined("or", Rule::Or, Parser::and)
+    }
+
+    fn and(&mut self) -> Result<Rule, Error> {
+        self.joined("and", Rule::And, Parser::unary)
+    }
+
+    fn unary(&mut self) -> Result<Rule, Error> {
+        Ok(match self.expect("a rule")? {
+            "not" => Rule::Not(Box::new(self.unary()?)),
+            "(" => {
+                let rule = self.or()?;
+                match self.expect("\")\"")? {
+                    ")" => rule,
+                    token => bail!("Expected \")\", found {:?}", token),
+                }
+            }
+            "non-decreasing" => Rule::NonDecreasing,
+            "run-exactly" => Rule::RunExactly(self.number("a run length")?),
+            "run-at-least" => Rule::RunAtLeast(self.number("a run length")?),
+            "count" => {
+                let digit = self.number("a digit")?;
+                let (min, max) = self.count_range()?;
+                Rule::Count { digit, min, max }
+            }
+            token => bail!("Unknown rule {:?}", token),
+        })
+    }
+
+    // `N` for exactly N, or `N..M`, `N..` or `..M` for a range, inclusive.
+    fn count_range(&mut self) -> Result<(usize, usize), Error> {
+        let token = self.expect("a count")?;
+        let bound = |s: &str, default| {
+            if s.is_empty() {
+                Ok(default)
+            } else {
+                s.parse()
+                    .map_err(|_| format_err!("Expected a count, found {:?}", token))
+            }
+        };
+        match token.find("..") {
+            Some(i) => Ok((bound(&token[..i], 0)?, bound(&token[i + 2..], usize::MAX)?)),
+            None => {
+                let n = bound(token, 0)?;
+                Ok((n, n))
+            }
+        }
+    }
+}
+
+// Parse a rule such as `non-decreasing and (run-exactly 2 or not count 9 ..1)`.
+// `not` binds tightest, then `and`, then `or`.
+pub fn parse_rule(rule: &str) -> Result<Rule, Error> {
+    let spaced = rule.replace('(', " ( ").replace(')', " ) ");
+    let mut parser = Parser {
+        tokens: spaced.split_whitespace().collect(),
+        pos: 0,
+    };
+    let parsed = parser.or()?;
+    if let Some(token) = parser.peek() {
+        bail!("Unexpected {:?} after rule", token);
+    }
+    Ok(parsed)
+}
+
+// Named rules, one per line as `name = rule`, skipping blank lines and
+// comments starting with `#`.
+pub fn parse_rule_sets(config: &str) -> Result<Vec<(String, Rule)>, Error> {
+    let mut rule_sets: Vec<(String, Rule)> = vec![];
+    for (i, line) in config.lines().enumerate() {
+        let line = line.split('#').next().unwrap().trim();
+        if line.is_empty() {
+            continue;
+        }
+        let parsed = match line.find('=') {
+            Some(eq) if !line[..eq].trim().is_empty() => {
+                let name = line[..eq].trim();
+                if rule_sets.iter().any(|(n, _)| n == name) {
+                    Err(format_err!("Rule set {:?} is defined twice", name))
+                } else {
+                    parse_rule(&line[eq + 1..]).map(|rule| (name.to_string(), rule))
+                }
+            }
+            _ => Err(format_err!("Expected NAME = RULE, got {:?}", line)),
+        };
+        rule_sets.push(parsed.map_err(|e| format_err!("Line {}: {}", i + 1, e))?);
+    }
+    Ok(rule_sets)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn check(rule: &str, test_data: &[(u64, bool)]) {
+        let rule = parse_rule(rule).unwrap();
+        for (n, expected) in test_data {
+            assert_eq!(rule.matches(&digits(*n, 10)), *expected, "{}", n);
+        }
+    }
+
+    #[test]
+    fn test_run_at_least() {
+        check(
+            "run-at-least 2",
+            &[
+                (123456, false),
+                (121212, false),
+                (113456, true),
+                (122456, true),
+                (123356, true),
+                (123446, true),
+                (123455, true),
+                (555555, true),
+            ],
+        );
+    }
+
+    #[test]
+    fn test_run_exactly() {
+        check(
+            "run-exactly 2",
+            &[
+                (123456, false),
+                (121212, false),
+                (113456, true),
+                (122456, true),
+                (123356, true),
+                (123446, true),
+                (123455, true),
+                (555555, false),
+                (122234, false),
+                (122244, true),
+            ],
+        );
+    }
+
+    #[test]
+    fn test_non_decreasing() {
+        check(
+            "non-decreasing",
+            &[
+                (955555, false),
+                (123454, false),
+                (121212, false),
+                (113456, true),
+                (122456, true),
+                (123356, true),
+                (123446, true),
+                (123455, true),
+                (555555, true),
+            ],
+        );
+    }
+
+    #[test]
+    fn test_combined_rules() {
+        check(
+            "non-decreasing and not (run-exactly 2 or count 9 1..)",
+            &[
+                (111234, true),
+                (112345, false),
+                (123459, false),
+                (123456, true),
+            ],
+        );
+        check(
+            "count 1 2 or count 0 ..0 and run-at-least 3",
+            &[
+                (110220, true),
+                (101010, false),
+                (222345, true),
+                (200034, false),
+            ],
+        );
+    }
+
+    #[test]
+    fn test_requires_non_decreasing() {
+        let test_data = [
+            ("non-decreasing", true),
+            ("run-exactly 2", false),
+            ("run-exactly 2 and non-decreasing", true),
+            ("non-decreasing or run-exactly 2", false),
+            (
+                "(non-decreasing and run-exactly 2) or (non-decreasing and count 9 2)",
+                true,
+            ),
+            ("not non-decreasing", false),
+            ("not not non-decreasing", true),
+            ("not (not non-decreasing or run-exactly 2)", true),
+            ("not (not non-decreasing and run-exactly 2)", false),
+        ];
+
+        for (rule, expected) in &test_data {
+            assert_eq!(
+                parse_rule(rule).unwrap().requires_non_decreasing(),
+                *expected,
+                "{}",
+                rule
+            );
+        }
+    }
+
+    #[test]
+    fn test_parse_rule() {
+        assert_eq!(
+            parse_rule("non-decreasing and (run-exactly 2 or not count 9 ..1)").unwrap(),
+            Rule::And(vec![
+                Rule::NonDecreasing,
+                Rule::Or(vec![
+                    Rule::RunExactly(2),
+                    Rule::Not(Box::new(Rule::Count {
+                        digit: 9,
+                        min: 0,
+                        max: 1
+                    })),
+                ]),
+            ])
+        );
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        let test_data = [
+            ("", "Expected a rule, found end of rule"),
+            ("non-decreasing and", "Expected a rule, found end of rule"),
+            ("increasing", "Unknown rule \"increasing\""),
+            ("run-exactly two", "Expected a run length, found \"two\""),
+            ("count 9 1...", "Expected a count, found \"1...\""),
+            ("(non-decreasing", "Expected \")\", found end of rule"),
+            (
+                "non-decreasing run-exactly 2",
+                "Unexpected \"run-exactly\" after rule",
+            ),
+        ];
+
+        for (rule, expected) in &test_data {
+            assert_eq!(parse_rule(rule).err().unwrap().to_string(), *expected);
+        }
+    }
+
+    #[test]
+    fn test_parse_rule_sets() {
+        let rule_sets = parse_rule_sets(
+            "# Comment\n\
+             \n\
+             loose = run-at-least 2  # Trailing comment\n\
+             strict = run-exactly 2\n",
+        )
+        .unwrap();
+        assert_eq!(
+            rule_sets,
+            vec![
+                ("loose".to_string(), Rule::RunAtLeast(2)),
+                ("strict".to_string(), Rule::RunExactly(2)),
+            ]
+        );
+
+        let test_data = [
+            (
+                "a = non-decreasing\nb",
+                "Line 2: Expected NAME = RULE, got \"b\"",
+            ),
+            (
+                "= non-decreasing",
+                "Line 1: Expected NAME = RULE, got \"= non-decreasing\"",
+            ),
+            (
+                "a = non-decreasing\na = run-exactly 2",
+                "Line 2: Rule set \"a\" is defined twice",
+            ),
+            (
+                "a = run-exactly",
+                "Line 1: Expected a run length, found end of rule",
+            ),
+        ];
+        for (config, expected) in &test_data {
+            assert_eq!(
+                parse_rule_sets(config).err().unwrap().to_string(),
+                *expected
+            );
+        }
+    }
+}
@@ -1,9 +1,9 @@
+use common::parse::parse_list;
 use failure::Error;
-use std::fs;
 
-type MemCell = i32;
+pub type MemCell = i32;
 
-enum Opcode {
+pub enum Opcode {
     Add,
     Mul,
     Input,
@@ -15,24 +15,25 @@ enum Opcode {
     Halt,
 }
 
-enum ParamMode {
+pub enum ParamMode {
     Position,
     Immediate,
 }
 
-struct Computer {
+pub struct Computer {
     ip: usize,
-    mem: Vec<MemCell>,
-    halted: bool,
+    pub mem: Vec<MemCell>,
+    pub halted: bool,
     input_queue: Vec<i32>,
+    pub outputs: Vec<i32>,
 }
 
-struct Instruction {
-    v: MemCell,
+pub struct Instruction {
+    pub v: MemCell,
 }
 
 impl Instruction {
-    fn opcode(&self) -> Opcode {
+    pub fn opcode(&self) -> Opcode {
         use Opcode::*;
         match self.v % 100 {
             1 => Add,
@@ -48,9 +49,9 @@ impl Instruction {
         }
     }
 
-    fn param_mode(&self, param_index: i32) -> ParamMode {
+    pub fn param_mode(&self, param_index: i32) -> ParamMode {
         use ParamMode::*;
-        match self.v as i32 / 100_i32.pow(param_index as u32) % 10 {
+        match self.v / 10_i32.pow((param_index + 1) as u32) % 10 {
             0 => Position,
             1 => Immediate,
             _ => panic!(
@@ -62,12 +63,13 @@ impl Instruction {
 }
 
 impl Computer {
-    fn new(mem: Vec<MemCell>, input_queue: Vec<i32>) -> Self {
+    pub fn new(mem: Vec<MemCell>, input_queue: Vec<i32>) -> Self {
         Computer {
             ip: 0,
             mem,
             halted: false,
             input_queue,
+            outputs: vec![],
         }
     }
 
@@ -82,7 +84,7 @@ impl Computer {
     }
 
     fn indirect_param(&self, i: i32) -> i32 {
-        self.mem[self.raw_param(i) as usize] as i32
+        self.mem[self.raw_param(i) as usize]
     }
 
     fn param(&self, inst: &Instruction, i: i32) -> i32 {
@@ -93,14 +95,14 @@ impl Computer {
     }
 
     fn read_input(&mut self) -> i32 {
-        self.input_queue.pop().unwrap()
+        self.input_queue.remove(0)
     }
 
-    fn write_output(&self, v: i32) {
-        println!("{}", v);
+    fn write_output(&mut self, v: i32) {
+        self.outputs.push(v);
     }
 
-    fn exec_instruction(&mut self) {
+    pub fn exec_instruction(&mut self) {
         let inst = self.instruction();
 
         match inst.opcode() {
@@ -167,7 +169,7 @@ impl Computer {
         }
     }
 
-    fn exec(mut self) -> Self {
+    pub fn exec(mut self) -> Self {
         while !self.halted {
             self.exec_instruction()
         }
@@ -175,26 +177,6 @@ impl Computer {
     }
 }
 
-fn load_program(prog: &str) -> Vec<MemCell> {
-    prog.split(',')
-        .map(|i| i.parse::<MemCell>().unwrap())
-        .collect()
-}
-
-fn main() -> Result<(), Error> {
-    let program = load_program(&fs::read_to_string("data.txt")?);
-    let input_queue = vec![5];
-
-    let computer = Computer::new(program.clone(), input_queue);
-    computer.exec();
-
-    Ok(())
-}
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn test_cpu() {}
+pub fn load_program(prog: &str) -> Result<Vec<MemCell>, Error> {
+    parse_list(prog, ',', "memory cell")
 }
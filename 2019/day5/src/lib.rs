@@ -0,0 +1,70 @@
+use failure::Error;
+use solution::{Answer, Solution};
+
+// Also compiled into the intcode crate's differential tests.
+pub mod computer;
+
+use computer::{load_program, Computer, MemCell};
+
+// The diagnostic program outputs zero after each test that passes, then a
+// diagnostic code.
+fn diagnostic_code(program: &[MemCell], system_id: MemCell) -> MemCell {
+    let computer = Computer::new(program.to_vec(), vec![system_id]).exec();
+    match computer.outputs.split_last() {
+        Some((code, tests)) if tests.iter().all(|t| *t == 0) => *code,
+        _ => panic!("Diagnostic tests failed: {:?}", computer.outputs),
+    }
+}
+
+pub struct Day5;
+
+impl Solution for Day5 {
+    type Input = Vec<MemCell>;
+
+    fn parse(input: &str) -> Result<Vec<MemCell>, Error> {
+        load_program(input)
+    }
+
+    fn part1(program: &Vec<MemCell>) -> Answer {
+        diagnostic_code(program, 1).into()
+    }
+
+    fn part2(program: &Vec<MemCell>) -> Answer {
+        diagnostic_code(program, 5).into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cpu() {
+        let test_data = [
+            ("3,9,8,9,10,9,4,9,99,-1,8", 8, 1),
+            ("3,9,7,9,10,9,4,9,99,-1,8", 8, 0),
+            ("3,3,1108,-1,8,3,4,3,99", 7, 0),
+            ("3,3,1107,-1,8,3,4,3,99", 7, 1),
+            ("3,12,6,12,15,1,13,14,13,4,13,99,-1,0,1,9", 0, 0),
+            ("3,3,1105,-1,9,1101,0,0,12,4,12,99,1", 3, 1),
+            ("3,0,4,0,99", 1, 1),
+            ("1002,6,3,6,4,6,33", 1, 99),
+        ];
+
+        for (code, input, expected) in &test_data {
+            let computer = Computer::new(load_program(code).unwrap(), vec![*input]).exec();
+            assert_eq!(computer.outputs, vec![*expected]);
+        }
+    }
+
+    #[test]
+    fn test_diagnostic_code() {
+        assert_eq!(diagnostic_code(&[3, 0, 104, 0, 4, 0, 99], 7), 7);
+    }
+
+    #[test]
+    #[should_panic(expected = "Diagnostic tests failed: [1, 7]")]
+    fn test_diagnostic_tests_failed() {
+        diagnostic_code(&[104, 1, 3, 0, 4, 0, 99], 7);
+    }
+}
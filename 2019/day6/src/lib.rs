@@ -0,0 +1,91 @@
+use failure::{format_err, Error};
+use itertools::Itertools;
+use solution::{Answer, Solution};
+use std::collections::HashMap;
+
+// Maps each body to the body it orbits.
+type StarMap = HashMap<String, String>;
+
+fn num_orbits(body: &str, h: &StarMap) -> i32 {
+    if !h.contains_key(body) {
+        0
+    } else {
+        match h.get(body) {
+            Some(parent) => 1 + num_orbits(parent, h),
+            None => panic!(),
+        }
+    }
+}
+
+fn total_orbits(map: &StarMap) -> i32 {
+    map.keys().map(|s| num_orbits(s, map)).sum()
+}
+
+fn orbital_chain(mut chain: Vec<String>, body: &str, map: &StarMap) -> Vec<String> {
+    if !map.contains_key(body) {
+        chain
+    } else {
+        match map.get(body) {
+            Some(parent) => {
+                chain.push(parent.to_string());
+                orbital_chain(chain, parent, map)
+            }
+            None => panic!(),
+        }
+    }
+}
+
+// Orbital transfers needed to get from the body YOU orbit to the body SAN
+// orbits, going via the nearest body they both orbit.
+fn transfers(map: &StarMap) -> Option<usize> {
+    let you_chain = orbital_chain(vec![], "YOU", map);
+    let san_chain = orbital_chain(vec![], "SAN", map);
+
+    you_chain.iter().enumerate().find_map(|(you_dist, body)| {
+        let san_dist = san_chain.iter().position(|x| x == body)?;
+        Some(you_dist + san_dist)
+    })
+}
+
+fn star_map(map_data: &str) -> Result<StarMap, Error> {
+    map_data
+        .lines()
+        .map(|l| {
+            let (a, b) = l
+                .split(')')
+                .collect_tuple()
+                .ok_or_else(|| format_err!("Invalid orbit: {:?}", l))?;
+            Ok((b.to_string(), a.to_string()))
+        })
+        .collect()
+}
+
+pub struct Day6;
+
+impl Solution for Day6 {
+    type Input = StarMap;
+
+    fn parse(input: &str) -> Result<StarMap, Error> {
+        star_map(input)
+    }
+
+    fn part1(map: &StarMap) -> Answer {
+        total_orbits(map).into()
+    }
+
+    fn part2(map: &StarMap) -> Answer {
+        transfers(map).expect("YOU and SAN share no orbit").into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    solution::example_tests!(Day6, 2019, 6);
+
+    #[test]
+    fn test_invalid_orbit() {
+        assert!(star_map("COM)B\nB-C").is_err());
+    }
+}
@@ -1,11 +1,10 @@
+use common::parse::parse_list;
 use failure::Error;
-use std::fs;
-use itertools::Itertools;
 use std::collections::VecDeque;
 
-type MemCell = i32;
+pub type MemCell = i32;
 
-enum Opcode {
+pub enum Opcode {
     Add,
     Mul,
     Input,
@@ -18,26 +17,26 @@ enum Opcode {
 }
 
 #[derive(Debug, Eq, PartialEq)]
-enum ParamMode {
+pub enum ParamMode {
     Position,
     Immediate,
 }
 
-struct Computer {
+pub struct Computer {
     ip: usize,
-    mem: Vec<MemCell>,
-    halted: bool,
-    last_output: i32,
+    pub mem: Vec<MemCell>,
+    pub halted: bool,
+    pub last_output: i32,
     input_queue: VecDeque<i32>,
     output_queue: VecDeque<i32>,
 }
 
-struct Instruction {
-    v: MemCell,
+pub struct Instruction {
+    pub v: MemCell,
 }
 
 impl Instruction {
-    fn opcode(&self) -> Opcode {
+    pub fn opcode(&self) -> Opcode {
         use Opcode::*;
         match self.v % 100 {
             1 => Add,
@@ -53,9 +52,9 @@ impl Instruction {
         }
     }
 
-    fn param_mode(&self, param_index: i32) -> ParamMode {
+    pub fn param_mode(&self, param_index: i32) -> ParamMode {
         use ParamMode::*;
-        match self.v as i32 / 10_i32.pow((param_index + 1) as u32) % 10 {
+        match self.v / 10_i32.pow((param_index + 1) as u32) % 10 {
             0 => Position,
             1 => Immediate,
             _ => panic!(
@@ -67,8 +66,7 @@ impl Instruction {
 }
 
 impl Computer {
-    fn new(mem: Vec<MemCell>) -> Self {
-        
+    pub fn new(mem: Vec<MemCell>) -> Self {
         Computer {
             ip: 0,
             mem,
@@ -90,7 +88,7 @@ impl Computer {
     }
 
     fn indirect_param(&self, i: i32) -> i32 {
-        self.mem[self.raw_param(i) as usize] as i32
+        self.mem[self.raw_param(i) as usize]
     }
 
     fn param(&self, inst: &Instruction, i: i32) -> i32 {
@@ -100,7 +98,7 @@ impl Computer {
         }
     }
 
-    fn inject_input(&mut self, v: i32) {
+    pub fn inject_input(&mut self, v: i32) {
         self.input_queue.push_back(v);
     }
 
@@ -113,11 +111,11 @@ impl Computer {
         self.output_queue.push_back(v);
     }
 
-    fn read_output(&mut self) -> Option<i32> {
+    pub fn read_output(&mut self) -> Option<i32> {
         self.output_queue.pop_front()
     }
 
-    fn exec_instruction(&mut self) {
+    pub fn exec_instruction(&mut self) {
         let inst = self.instruction();
 
         match inst.opcode() {
@@ -126,7 +124,7 @@ impl Computer {
                     let src = input;
                     let dst = self.raw_param(1) as usize;
                     self.mem[dst] = src as MemCell;
-                    self.ip += 2;   
+                    self.ip += 2;
                 }
             }
             Opcode::Output => {
@@ -187,90 +185,6 @@ impl Computer {
     }
 }
 
-fn load_program(prog: &str) -> Vec<MemCell> {
-    prog.split(',')
-        .map(|i| i.parse::<MemCell>().unwrap())
-        .collect()
-}
-
-fn run_thruster_amps(program: &[MemCell], phases: &[i32]) -> i32 {
-    let mut computers: Vec<Computer> = phases
-        .iter()
-        .map(|phase| {
-            let mut c = Computer::new(program.into());
-            c.inject_input(*phase);
-            c
-        })
-        .collect();
-
-    let mut output = Some(0);
-    let mut computers_are_running = true;
-    while computers_are_running {
-        computers_are_running = false;
-        for computer in &mut computers {
-            if let Some(v) = output {
-                computer.inject_input(v);
-            }
-            computer.exec_instruction();
-            output = computer.read_output();
-            if !computer.halted {
-                computers_are_running = true;
-            }
-        }
-    }
-
-    computers[computers.len() - 1].last_output
-}
-
-fn maximise_thruster_power(program: &[MemCell]) -> (i32, Vec<i32>) {
-    let mut max_power = 0;
-    let mut best_phases = vec![];
-    for phases in (5..=9).permutations(NUM_AMPS as usize) {
-        let output = run_thruster_amps(&program, &phases);
-        if output > max_power {
-            max_power = output;
-            best_phases = phases.clone();
-        }
-    }
-    (max_power, best_phases)
-}
-
-const NUM_AMPS: i32 = 5;
-fn main() -> Result<(), Error> {
-    let program = load_program(&fs::read_to_string("data.txt")?);
-
-    let (max_power, phases) = maximise_thruster_power(&program); 
-    println!("max_power:{}, phases:{:?}", max_power, &phases);
-
-    Ok(())
-}
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn test_param_mode() {
-        assert_eq!(Instruction{v: 100}.param_mode(1), ParamMode::Immediate);
-        assert_eq!(Instruction{v: 100}.param_mode(2), ParamMode::Position);
-        assert_eq!(Instruction{v: 100}.param_mode(3), ParamMode::Position);
-        assert_eq!(Instruction{v: 1000}.param_mode(1), ParamMode::Position);
-        assert_eq!(Instruction{v: 1100}.param_mode(2), ParamMode::Immediate);
-        assert_eq!(Instruction{v: 1100}.param_mode(3), ParamMode::Position);
-    }
-
-    #[test]
-    fn test_cpu() {
-        let test_data = [
-            ("3,26,1001,26,-4,26,3,27,1002,27,2,27,1,27,26,27,4,27,1001,28,-1,28,1005,28,6,99,0,0,5", 139629729),
-            ("3,52,1001,52,-5,52,3,53,1,52,56,54,1007,54,5,55,1005,55,26,1001,54,-5,54,1105,1,12,1,53,54,53,1008,54,0,55,1001,55,1,55,2,53,55,53,4,53,1001,56,-1,56,1005,56,6,99,0,0,0,0,10",18216)
-        ];
-
-        for (code, expected_power) in &test_data {
-            let program = load_program(code);
-            let (max_power, phases) = maximise_thruster_power(&program);
-            println!("max_power:{} phases:{:?}", max_power, &phases);
-            assert_eq!(max_power, *expected_power);
-        }
-    }
+pub fn load_program(prog: &str) -> Result<Vec<MemCell>, Error> {
+    parse_list(prog, ',', "memory cell")
 }
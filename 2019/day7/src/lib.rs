@@ -0,0 +1,94 @@
+use failure::Error;
+use itertools::Itertools;
+use solution::{Answer, Solution};
+use std::ops::RangeInclusive;
+
+// Also compiled into the intcode crate's differential tests.
+pub mod computer;
+
+use computer::{load_program, Computer, MemCell};
+
+// Amplifiers are connected in a loop. In part 1, each amplifier halts
+// after its first output, so the feedback is ignored.
+fn run_thruster_amps(program: &[MemCell], phases: &[i32]) -> i32 {
+    let mut computers: Vec<Computer> = phases
+        .iter()
+        .map(|phase| {
+            let mut c = Computer::new(program.into());
+            c.inject_input(*phase);
+            c
+        })
+        .collect();
+
+    let mut output = Some(0);
+    let mut computers_are_running = true;
+    while computers_are_running {
+        computers_are_running = false;
+        for computer in &mut computers {
+            if let Some(v) = output {
+                computer.inject_input(v);
+            }
+            computer.exec_instruction();
+            output = computer.read_output();
+            if !computer.halted {
+                computers_are_running = true;
+            }
+        }
+    }
+
+    computers[computers.len() - 1].last_output
+}
+
+fn maximise_thruster_power(
+    program: &[MemCell],
+    phase_settings: RangeInclusive<i32>,
+) -> (i32, Vec<i32>) {
+    let mut max_power = 0;
+    let mut best_phases = vec![];
+    for phases in phase_settings.permutations(NUM_AMPS as usize) {
+        let output = run_thruster_amps(program, &phases);
+        if output > max_power {
+            max_power = output;
+            best_phases = phases.clone();
+        }
+    }
+    (max_power, best_phases)
+}
+
+const NUM_AMPS: i32 = 5;
+
+pub struct Day7;
+
+impl Solution for Day7 {
+    type Input = Vec<MemCell>;
+
+    fn parse(input: &str) -> Result<Vec<MemCell>, Error> {
+        load_program(input)
+    }
+
+    fn part1(program: &Vec<MemCell>) -> Answer {
+        maximise_thruster_power(program, 0..=4).0.into()
+    }
+
+    fn part2(program: &Vec<MemCell>) -> Answer {
+        maximise_thruster_power(program, 5..=9).0.into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::computer::{Instruction, ParamMode};
+
+    solution::example_tests!(Day7, 2019, 7);
+
+    #[test]
+    fn test_param_mode() {
+        assert_eq!(Instruction { v: 100 }.param_mode(1), ParamMode::Immediate);
+        assert_eq!(Instruction { v: 100 }.param_mode(2), ParamMode::Position);
+        assert_eq!(Instruction { v: 100 }.param_mode(3), ParamMode::Position);
+        assert_eq!(Instruction { v: 1000 }.param_mode(1), ParamMode::Position);
+        assert_eq!(Instruction { v: 1100 }.param_mode(2), ParamMode::Immediate);
+        assert_eq!(Instruction { v: 1100 }.param_mode(3), ParamMode::Position);
+    }
+}
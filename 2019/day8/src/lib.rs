@@ -0,0 +1,108 @@
+use failure::{bail, Error};
+use itertools::Itertools;
+use solution::{Answer, Solution};
+use std::collections::HashMap;
+
+const IMAGE_WIDTH: i32 = 25;
+const IMAGE_HEIGHT: i32 = 6;
+const LAYER_SIZE: i32 = IMAGE_WIDTH * IMAGE_HEIGHT;
+
+fn image_check(image_data: &str) -> i32 {
+    let freq_map = image_data
+        .chars()
+        .chunks((IMAGE_WIDTH * IMAGE_HEIGHT) as usize)
+        .into_iter()
+        .map(|layer| {
+            layer.fold(HashMap::<char, i32>::new(), |mut map, c| {
+                *map.entry(c).or_insert(0) += 1;
+                map
+            })
+        })
+        .min_by_key(|freq_map| freq_map.get(&'0').cloned().unwrap_or(0))
+        .unwrap();
+
+    let count = |c| freq_map.get(&c).cloned().unwrap_or(0);
+    count('1') * count('2')
+}
+
+const TRANSPARENT_COLOR: u8 = b'2';
+
+fn pixel_color(x: i32, y: i32, image_data: &[u8]) -> char {
+    let num_layers = image_data.len() as i32 / LAYER_SIZE;
+    for layer in 0..num_layers {
+        let index = (layer * LAYER_SIZE) + (y * IMAGE_WIDTH) + x;
+        let pixel = image_data[index as usize];
+        if pixel != TRANSPARENT_COLOR {
+            return pixel as char;
+        }
+    }
+    '0'
+}
+
+fn render_image_output(image_data: &[u8]) -> String {
+    let mut s = String::new();
+    for y in 0..IMAGE_HEIGHT {
+        for x in 0..IMAGE_WIDTH {
+            s += match pixel_color(x, y, image_data) {
+                '0' => " ",
+                '1' => "*",
+                _ => "?",
+            }
+        }
+        s += "\n";
+    }
+    s
+}
+
+pub struct Day8;
+
+impl Solution for Day8 {
+    type Input = String;
+
+    fn parse(input: &str) -> Result<String, Error> {
+        if let Some(c) = input.chars().find(|c| !c.is_ascii_digit()) {
+            bail!("Invalid pixel: {:?}", c);
+        }
+        if input.is_empty() || !input.len().is_multiple_of(LAYER_SIZE as usize) {
+            bail!("Image size {} isn't a whole number of layers", input.len());
+        }
+        Ok(input.to_string())
+    }
+
+    fn part1(image_data: &String) -> Answer {
+        image_check(image_data).into()
+    }
+
+    fn part2(image_data: &String) -> Answer {
+        render_image_output(image_data.as_bytes()).into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_image_check() {
+        // The second layer has the fewest zeros.
+        let image = "0".repeat(150) + &"1".repeat(10) + &"2".repeat(5) + &"0".repeat(135);
+        assert_eq!(image_check(&image), 50);
+    }
+
+    #[test]
+    fn test_render_image_output() {
+        // The first layer is transparent apart from its first pixel, which
+        // hides the second layer's white pixel beneath it.
+        let image = "1".to_string() + &"2".repeat(149) + "11" + &"0".repeat(148);
+        let blank_row = " ".repeat(25) + "\n";
+        let expected = "**".to_string() + &" ".repeat(23) + "\n" + &blank_row.repeat(5);
+        assert_eq!(render_image_output(image.as_bytes()), expected);
+    }
+
+    #[test]
+    fn test_parse() {
+        assert!(Day8::parse(&"0".repeat(300)).is_ok());
+        assert!(Day8::parse(&"0".repeat(299)).is_err());
+        assert!(Day8::parse(&("x".to_string() + &"0".repeat(149))).is_err());
+    }
+}
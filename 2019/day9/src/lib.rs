@@ -0,0 +1,81 @@
+use failure::Error;
+use solution::{Answer, Solution};
+
+// Also compiled into the intcode crate's differential tests.
+pub mod computer;
+
+use computer::{load_program, Computer, Int};
+
+fn run_program(program: &[Int], inputs: &[Int]) -> Vec<Int> {
+    let mut c = Computer::new(program.to_vec());
+    for input in inputs {
+        c.inject_input(*input);
+    }
+    c.run();
+    let mut outputs = vec![];
+    while let Some(v) = c.read_output() {
+        outputs.push(v);
+    }
+    outputs
+}
+
+// BOOST outputs any opcodes it finds to be faulty, then a single result.
+fn boost(program: &[Int], mode: Int) -> Int {
+    match run_program(program, &[mode])[..] {
+        [result] => result,
+        ref outputs => panic!("Faulty opcodes: {:?}", outputs),
+    }
+}
+
+pub struct Day9;
+
+impl Solution for Day9 {
+    type Input = Vec<Int>;
+
+    fn parse(input: &str) -> Result<Vec<Int>, Error> {
+        load_program(input)
+    }
+
+    fn part1(program: &Vec<Int>) -> Answer {
+        boost(program, 1).into()
+    }
+
+    fn part2(program: &Vec<Int>) -> Answer {
+        boost(program, 2).into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::computer::{Instruction, ParamMode};
+
+    #[test]
+    fn test_param_mode() {
+        assert_eq!(Instruction { v: 100 }.param_mode(1), ParamMode::Immediate);
+        assert_eq!(Instruction { v: 100 }.param_mode(2), ParamMode::Position);
+        assert_eq!(Instruction { v: 100 }.param_mode(3), ParamMode::Position);
+        assert_eq!(Instruction { v: 1000 }.param_mode(1), ParamMode::Position);
+        assert_eq!(Instruction { v: 1100 }.param_mode(2), ParamMode::Immediate);
+        assert_eq!(Instruction { v: 1100 }.param_mode(3), ParamMode::Position);
+        assert_eq!(Instruction { v: 200 }.param_mode(1), ParamMode::Relative);
+    }
+
+    #[test]
+    fn test_cpu() {
+        let quine = "109,1,204,-1,1001,100,1,100,1008,100,16,101,1006,101,0,99";
+        let test_data = [
+            (quine, load_program(quine).unwrap()[..16].to_vec()),
+            (
+                "1102,34915192,34915192,7,4,7,99,0",
+                vec![1_219_070_632_396_864],
+            ),
+            ("104,1125899906842624,99", vec![1_125_899_906_842_624]),
+        ];
+
+        for (code, expected) in &test_data {
+            let program = load_program(code).unwrap();
+            assert_eq!(run_program(&program, &[]), *expected);
+        }
+    }
+}
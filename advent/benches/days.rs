@@ -0,0 +1,38 @@
+use criterion::{criterion_group, criterion_main, Criterion};
+use solution::inputs::Inputs;
+use solution::Solution;
+
+// Benchmark parsing and each part of one day's puzzle against its local
+// input.
+fn bench_day<S: Solution>(c: &mut Criterion, year: u32, day: u32) {
+    let input = Inputs::from_env().read(year, day, None).unwrap();
+    let input = input.as_str();
+    let parsed = S::parse(input).expect("Failed to parse puzzle input");
+
+    let mut group = c.benchmark_group(format!("{}-day{}", year, day));
+    group.bench_function("parse", |b| b.iter(|| S::parse(input).unwrap()));
+    group.bench_function("part1", |b| b.iter(|| S::part1(&parsed)));
+    group.bench_function("part2", |b| b.iter(|| S::part2(&parsed)));
+    group.finish();
+}
+
+fn days(c: &mut Criterion) {
+    bench_day::<advent2019_day1::Day1>(c, 2019, 1);
+    bench_day::<advent2019_day2::Day2>(c, 2019, 2);
+    bench_day::<advent2019_day3::Day3>(c, 2019, 3);
+    bench_day::<advent2019_day4::Day4>(c, 2019, 4);
+    bench_day::<advent2019_day5::Day5>(c, 2019, 5);
+    bench_day::<advent2019_day6::Day6>(c, 2019, 6);
+    bench_day::<advent2019_day7::Day7>(c, 2019, 7);
+    bench_day::<advent2019_day8::Day8>(c, 2019, 8);
+    bench_day::<advent2019_day9::Day9>(c, 2019, 9);
+}
+
+criterion_group! {
+    name = benches;
+    // Some days take tens of milliseconds per iteration, so keep the suite
+    // quick with fewer samples.
+    config = Criterion::default().sample_size(20);
+    targets = days
+}
+criterion_main!(benches);
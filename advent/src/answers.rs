@@ -0,0 +1,129 @@
+use failure::{bail, format_err, Error};
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+use toml::Value;
+
+// The known-good answer for each year, day and part, recorded as
+//
+//     [2019.day1]
+//     part1 = 3563458
+//
+// Text answers, like rendered images, are multi-line strings. Trailing
+// whitespace on each line is ignored so that editors can't break them.
+pub struct Answers(HashMap<(u32, u32, u32), String>);
+
+fn normalise(answer: &str) -> String {
+    answer
+        .trim_matches('\n')
+        .lines()
+        .map(str::trim_end)
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+fn parse_key(key: &str, prefix: &str) -> Result<u32, Error> {
+    key.strip_prefix(prefix)
+        .and_then(|n| n.parse().ok())
+        .ok_or_else(|| format_err!("Invalid key {:?}: expected {}N", key, prefix))
+}
+
+fn table<'a>(value: &'a Value, key: &str) -> Result<&'a toml::value::Table, Error> {
+    value
+        .as_table()
+        .ok_or_else(|| format_err!("[{}] must be a table", key))
+}
+
+impl Answers {
+    pub fn load(path: &Path) -> Result<Answers, Error> {
+        let text = fs::read_to_string(path)
+            .map_err(|e| format_err!("Failed to read {}: {}", path.display(), e))?;
+        Answers::parse(&text).map_err(|e| format_err!("{}: {}", path.display(), e))
+    }
+
+    pub fn parse(text: &str) -> Result<Answers, Error> {
+        let answers_file: Value = text.parse()?;
+        let mut answers = HashMap::new();
+        for (year_key, days) in table(&answers_file, "")? {
+            let year = parse_key(year_key, "")?;
+            for (day_key, parts) in table(days, year_key)? {
+                let day = parse_key(day_key, "day")?;
+                let key = format!("{}.{}", year_key, day_key);
+                for (part_key, answer) in table(parts, &key)? {
+                    let part = parse_key(part_key, "part")?;
+                    let answer = match answer {
+                        Value::Integer(v) => v.to_string(),
+                        Value::String(s) => normalise(s),
+                        _ => bail!("{}.{} must be an integer or string", key, part_key),
+                    };
+                    answers.insert((year, day, part), answer);
+                }
+            }
+        }
+        Ok(Answers(answers))
+    }
+
+    // Whether the answer matches the recorded one, or None if there isn't
+    // one recorded.
+    pub fn check(&self, year: u32, day: u32, part: u32, answer: &str) -> Option<Result<(), &str>> {
+        let expected = self.0.get(&(year, day, part))?;
+        if *expected == normalise(answer) {
+            Some(Ok(()))
+        } else {
+            Some(Err(expected))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_check() {
+        let answers = Answers::parse(
+            "[2019.day1]\n\
+             part1 = 42\n\
+             [2019.day8]\n\
+             part2 = '''\n\
+             * *  \n\
+             ** *\n\
+             '''\n",
+        )
+        .unwrap();
+
+        assert_eq!(answers.check(2019, 1, 1, "42"), Some(Ok(())));
+        assert_eq!(answers.check(2019, 1, 1, "43"), Some(Err("42")));
+        assert_eq!(answers.check(2019, 1, 2, "42"), None);
+        assert_eq!(answers.check(2020, 1, 1, "42"), None);
+        assert_eq!(answers.check(2019, 8, 2, "* *\n** *  \n"), Some(Ok(())));
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        let test_data = [
+            (
+                "[twenty.day1]\npart1 = 1",
+                "Invalid key \"twenty\": expected N",
+            ),
+            (
+                "[2019.dayone]\npart1 = 1",
+                "Invalid key \"dayone\": expected dayN",
+            ),
+            (
+                "[2019.day1]\nanswer = 1",
+                "Invalid key \"answer\": expected partN",
+            ),
+            ("2019 = 1", "[2019] must be a table"),
+            ("[2019]\nday1 = 1", "[2019.day1] must be a table"),
+            (
+                "[2019.day1]\npart1 = 1.5",
+                "2019.day1.part1 must be an integer or string",
+            ),
+        ];
+
+        for (text, expected) in &test_data {
+            assert_eq!(Answers::parse(text).err().unwrap().to_string(), *expected);
+        }
+    }
+}
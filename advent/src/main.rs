@@ -0,0 +1,167 @@
+mod answers;
+mod report;
+
+use answers::Answers;
+use failure::{bail, format_err, Error};
+use solution::inputs::{read_input, Inputs};
+use solution::Run;
+use std::env;
+use std::fmt;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+const USAGE: &str = "usage: advent run YEAR DAY PART [--input PATH | --name NAME]
+       advent run --all [YEAR]
+       advent verify [--answers PATH]
+       advent bench-report [--baseline NAME]";
+
+type Runner = fn(&str, u32) -> Result<Run, Error>;
+
+struct Puzzle {
+    year: u32,
+    day: u32,
+    part: u32,
+    run: Runner,
+}
+
+// Each year's days are registered once and provide both parts.
+const DAYS: &[(u32, u32, Runner)] = &[
+    (2019, 1, solution::run::<advent2019_day1::Day1>),
+    (2019, 2, solution::run::<advent2019_day2::Day2>),
+    (2019, 3, solution::run::<advent2019_day3::Day3>),
+    (2019, 4, solution::run::<advent2019_day4::Day4>),
+    (2019, 5, solution::run::<advent2019_day5::Day5>),
+    (2019, 6, solution::run::<advent2019_day6::Day6>),
+    (2019, 7, solution::run::<advent2019_day7::Day7>),
+    (2019, 8, solution::run::<advent2019_day8::Day8>),
+    (2019, 9, solution::run::<advent2019_day9::Day9>),
+];
+
+impl Puzzle {
+    fn all() -> impl Iterator<Item = Puzzle> {
+        DAYS.iter().flat_map(|&(year, day, run)| {
+            (1..=2).map(move |part| Puzzle {
+                year,
+                day,
+                part,
+                run,
+            })
+        })
+    }
+
+    fn find(year: u32, day: u32, part: u32) -> Result<Puzzle, Error> {
+        Puzzle::all()
+            .find(|p| p.year == year && p.day == day && p.part == part)
+            .ok_or_else(|| format_err!("No solution for {} day {} part {}", year, day, part))
+    }
+
+    fn input(&self, inputs: &Inputs, name: Option<&str>) -> Result<String, Error> {
+        inputs.read(self.year, self.day, name)
+    }
+
+    fn run(&self, input: &str) -> Result<Run, Error> {
+        (self.run)(input, self.part)
+    }
+}
+
+impl fmt::Display for Puzzle {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} day {} part {}", self.year, self.day, self.part)
+    }
+}
+
+fn run_all(inputs: &Inputs, year: Option<u32>) -> Result<(), Error> {
+    let mut failures = 0;
+    let mut total = Duration::default();
+    for p in Puzzle::all().filter(|p| year.is_none_or(|year| p.year == year)) {
+        match p.input(inputs, None).and_then(|input| p.run(&input)) {
+            Ok(run) => {
+                total += run.parse_time + run.solve_time;
+                // Multi-line answers, like rendered images, start on their
+                // own line.
+                let answer = run.answer.to_string();
+                let sep = if answer.contains('\n') { "\n" } else { " " };
+                println!(
+                    "{} (parse {:.2?}, solve {:.2?}):{}{}",
+                    p, run.parse_time, run.solve_time, sep, answer
+                );
+            }
+            Err(e) => {
+                failures += 1;
+                println!("{}: error: {}", p, e);
+            }
+        }
+    }
+    println!("total: {:.2?}", total);
+    if failures > 0 {
+        bail!("{} puzzles failed", failures);
+    }
+    Ok(())
+}
+
+fn default_answers() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("..")
+        .join("answers.toml")
+}
+
+// Run every puzzle and compare its answer with the recorded one. Puzzles
+// without a recorded answer are reported but don't count as failures.
+fn verify(inputs: &Inputs, answers: &Answers) -> Result<(), Error> {
+    let mut failures = 0;
+    for p in Puzzle::all() {
+        let answer = match p.input(inputs, None).and_then(|input| p.run(&input)) {
+            Ok(run) => run.answer.to_string(),
+            Err(e) => {
+                failures += 1;
+                println!("{}: error: {}", p, e);
+                continue;
+            }
+        };
+        match answers.check(p.year, p.day, p.part, &answer) {
+            Some(Ok(())) => println!("{}: ok", p),
+            Some(Err(expected)) => {
+                failures += 1;
+                println!(
+                    "{}: WRONG\nexpected:\n{}\ngot:\n{}",
+                    p,
+                    expected,
+                    answer.trim_end()
+                );
+            }
+            None => println!("{}: no recorded answer", p),
+        }
+    }
+    if failures > 0 {
+        bail!("{} puzzles failed verification", failures);
+    }
+    Ok(())
+}
+
+fn main() -> Result<(), Error> {
+    let args: Vec<String> = env::args().skip(1).collect();
+    let args: Vec<&str> = args.iter().map(String::as_str).collect();
+    let inputs = Inputs::from_env();
+    match args.as_slice() {
+        ["run", "--all"] => run_all(&inputs, None),
+        ["run", "--all", year] => run_all(&inputs, Some(year.parse()?)),
+        ["run", year, day, part, rest @ ..] => {
+            let puzzle = Puzzle::find(year.parse()?, day.parse()?, part.parse()?)?;
+            let input = match rest {
+                [] => puzzle.input(&inputs, None)?,
+                ["--name", name] => puzzle.input(&inputs, Some(name))?,
+                ["--input", path] => read_input(Path::new(path))?,
+                _ => bail!("{}", USAGE),
+            };
+            println!("{}", puzzle.run(&input)?.answer);
+            Ok(())
+        }
+        ["verify"] => verify(&inputs, &Answers::load(&default_answers())?),
+        ["verify", "--answers", path] => verify(&inputs, &Answers::load(Path::new(path))?),
+        ["bench-report"] => report::bench_report(DAYS.iter().map(|d| (d.0, d.1)), None),
+        ["bench-report", "--baseline", name] => {
+            report::bench_report(DAYS.iter().map(|d| (d.0, d.1)), Some(name))
+        }
+        _ => bail!("{}", USAGE),
+    }
+}
@@ -0,0 +1,80 @@
+use failure::{format_err, Error};
+use serde_json::Value;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+const PHASES: &[&str] = &["parse", "part1", "part2"];
+
+fn criterion_dir() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("..")
+        .join("target")
+        .join("criterion")
+}
+
+// The mean time criterion measured for one benchmark, or None if it hasn't
+// been run (or saved under that baseline).
+fn mean_time(year: u32, day: u32, phase: &str, baseline: &str) -> Result<Option<Duration>, Error> {
+    let path = criterion_dir()
+        .join(format!("{}-day{}", year, day))
+        .join(phase)
+        .join(baseline)
+        .join("estimates.json");
+    let text = match fs::read_to_string(&path) {
+        Ok(text) => text,
+        Err(_) => return Ok(None),
+    };
+    let estimates: Value = serde_json::from_str(&text)
+        .map_err(|e| format_err!("Failed to parse {}: {}", path.display(), e))?;
+    let nanos = estimates["mean"]["point_estimate"]
+        .as_f64()
+        .ok_or_else(|| format_err!("No mean estimate in {}", path.display()))?;
+    Ok(Some(Duration::from_nanos(nanos as u64)))
+}
+
+fn change(new: Duration, base: Duration) -> String {
+    let ratio = new.as_secs_f64() / base.as_secs_f64();
+    format!("{:+.1}%", (ratio - 1.0) * 100.0)
+}
+
+// Summarise the latest `cargo bench` run as a table, comparing it with a
+// baseline saved by `cargo bench -- --save-baseline NAME` if given.
+pub fn bench_report(
+    days: impl Iterator<Item = (u32, u32)>,
+    baseline: Option<&str>,
+) -> Result<(), Error> {
+    let show = |t: Option<Duration>| t.map_or("-".to_string(), |t| format!("{:.2?}", t));
+    println!(
+        "{:<17} {:>12} {:>12} {:>8}",
+        "bench",
+        "time",
+        baseline.unwrap_or(""),
+        ""
+    );
+    for (year, day) in days {
+        for phase in PHASES {
+            let new = mean_time(year, day, phase, "new")?;
+            let base = match baseline {
+                Some(baseline) => mean_time(year, day, phase, baseline)?,
+                None => None,
+            };
+            let delta = match (new, base) {
+                (Some(new), Some(base)) => change(new, base),
+                _ => String::new(),
+            };
+            println!(
+                "{:<17} {:>12} {:>12} {:>8}",
+                format!("{}-day{}/{}", year, day, phase),
+                show(new),
+                if baseline.is_some() {
+                    show(base)
+                } else {
+                    String::new()
+                },
+                delta
+            );
+        }
+    }
+    Ok(())
+}
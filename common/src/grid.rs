@@ -0,0 +1,53 @@
+// A point on a grid, with y increasing upwards.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Point {
+    pub x: i64,
+    pub y: i64,
+}
+
+impl Point {
+    pub fn new(x: i64, y: i64) -> Self {
+        Point { x, y }
+    }
+
+    // Distances from the origin.
+    pub fn manhattan(&self) -> i64 {
+        self.x.abs() + self.y.abs()
+    }
+
+    pub fn chebyshev(&self) -> i64 {
+        self.x.abs().max(self.y.abs())
+    }
+
+    // Squared to keep it exact, and in 128 bits so that it can't overflow.
+    pub fn euclidean_squared(&self) -> u128 {
+        let (x, y) = (
+            u128::from(self.x.unsigned_abs()),
+            u128::from(self.y.unsigned_abs()),
+        );
+        x * x + y * y
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_distances() {
+        let test_data = [
+            (Point::new(0, 0), 0, 0, 0),
+            (Point::new(3, -4), 7, 4, 25),
+            (Point::new(-6, -6), 12, 6, 72),
+        ];
+
+        for (p, manhattan, chebyshev, euclidean_squared) in &test_data {
+            assert_eq!(p.manhattan(), *manhattan);
+            assert_eq!(p.chebyshev(), *chebyshev);
+            assert_eq!(p.euclidean_squared(), *euclidean_squared);
+        }
+
+        let far = Point::new(i64::MIN, i64::MIN);
+        assert_eq!(far.euclidean_squared(), 1 << 127);
+    }
+}
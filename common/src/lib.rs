@@ -0,0 +1,3 @@
+pub mod grid;
+pub mod parse;
+pub mod random;
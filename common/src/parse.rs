@@ -0,0 +1,59 @@
+use failure::{format_err, Error};
+use std::fmt;
+use std::str::FromStr;
+
+// Parse a list of values separated by a character, such as an Intcode
+// program. `what` names a value in errors.
+pub fn parse_list<T>(text: &str, separator: char, what: &str) -> Result<Vec<T>, Error>
+where
+    T: FromStr,
+    T::Err: fmt::Display,
+{
+    text.split(separator)
+        .map(|v| {
+            v.parse()
+                .map_err(|e| format_err!("Invalid {} {:?}: {}", what, v, e))
+        })
+        .collect()
+}
+
+// Parse each line of the text, prefixing errors with the line number.
+pub fn parse_lines<T, F>(text: &str, parse: F) -> Result<Vec<T>, Error>
+where
+    F: Fn(&str) -> Result<T, Error>,
+{
+    text.lines()
+        .enumerate()
+        .map(|(i, line)| parse(line).map_err(|e| format_err!("Line {}: {}", i + 1, e)))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_list() {
+        assert_eq!(
+            parse_list::<i64>("1,-2,3", ',', "cell").unwrap(),
+            vec![1, -2, 3]
+        );
+        assert_eq!(
+            parse_list::<i64>("1,x", ',', "cell")
+                .err()
+                .unwrap()
+                .to_string(),
+            "Invalid cell \"x\": invalid digit found in string"
+        );
+    }
+
+    #[test]
+    fn test_parse_lines() {
+        let parse = |line: &str| -> Result<u32, Error> { Ok(line.parse()?) };
+        assert_eq!(parse_lines("1\n2", parse).unwrap(), vec![1, 2]);
+        assert_eq!(
+            parse_lines("1\n2\nthree", parse).err().unwrap().to_string(),
+            "Line 3: invalid digit found in string"
+        );
+    }
+}
@@ -0,0 +1,20 @@
+// A 64-bit linear congruential generator. It's fast and the same on every
+// platform, for tests and fuzzing that need repeatable random choices.
+pub struct Random(u64);
+
+impl Random {
+    pub fn new(seed: u64) -> Self {
+        Random(seed)
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.0 = self.0.wrapping_mul(6364136223846793005).wrapping_add(1);
+        self.0
+    }
+
+    // A number from 0 up to, but not including, n. The low bits of an LCG
+    // repeat quickly, so this uses the high ones.
+    pub fn below(&mut self, n: u64) -> u64 {
+        (self.next_u64() >> 33) % n
+    }
+}
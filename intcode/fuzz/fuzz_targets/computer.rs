@@ -0,0 +1,13 @@
+#![no_main]
+use intcode::fuzz::{execute, Case, Outcome};
+use libfuzzer_sys::fuzz_target;
+
+// libFuzzer supplies its own coverage feedback, so all this target needs to
+// do is turn any crash the harness catches back into a panic for it to see.
+// Use `intcode-fuzz` instead to get minimised regression files directly.
+fuzz_target!(|data: &[u8]| {
+    let case = Case::from_bytes(data);
+    if let (Outcome::Crashed(crash), _) = execute(&case, 10_000) {
+        panic!("{}: {:?}", crash, case);
+    }
+});
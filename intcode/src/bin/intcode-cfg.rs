@@ -0,0 +1,44 @@
+use failure::{bail, format_err, Error};
+use intcode::cfg::{profile, Cfg};
+use intcode::{load_program, Computer};
+use std::env;
+use std::fs;
+
+const USAGE: &str = "usage: intcode-cfg PROGRAM [--profile] [--input N]...";
+
+// Print the control-flow graph of a program as Graphviz DOT. With
+// `--profile`, or any `--input`, the program is run first and edges are
+// weighted by how often they were followed.
+fn main() -> Result<(), Error> {
+    let mut path = None;
+    let mut run = false;
+    let mut inputs = vec![];
+
+    let mut args = env::args().skip(1);
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--profile" => run = true,
+            "--input" => {
+                let v = args.next().ok_or_else(|| format_err!("{}", USAGE))?;
+                inputs.push(v.parse()?);
+                run = true;
+            }
+            _ if arg.starts_with("--") || path.is_some() => bail!("{}", USAGE),
+            _ => path = Some(arg),
+        }
+    }
+    let path = path.ok_or_else(|| format_err!("{}", USAGE))?;
+    let program = load_program(&fs::read_to_string(path)?);
+
+    if run {
+        let mut c = Computer::new(program.clone());
+        for input in inputs {
+            c.inject_input(input);
+        }
+        let p = profile(&mut c);
+        print!("{}", Cfg::with_profile(&program, &p).to_dot(Some(&p)));
+    } else {
+        print!("{}", Cfg::new(&program).to_dot(None));
+    }
+    Ok(())
+}
@@ -0,0 +1,19 @@
+use failure::{bail, Error};
+use intcode::dump::{diff, Dump};
+use std::env;
+use std::fs;
+
+const USAGE: &str = "usage: intcode-diff BEFORE AFTER";
+
+// Compare two dumps, or a program image and a dump, showing changed
+// registers and runs of changed memory cells.
+fn main() -> Result<(), Error> {
+    let args: Vec<String> = env::args().skip(1).collect();
+    if args.len() != 2 {
+        bail!("{}", USAGE);
+    }
+    let before = Dump::load(&fs::read_to_string(&args[0])?)?;
+    let after = Dump::load(&fs::read_to_string(&args[1])?)?;
+    print!("{}", diff(&before, &after));
+    Ok(())
+}
@@ -0,0 +1,50 @@
+use failure::{bail, format_err, Error};
+use intcode::dump::{self, Dump};
+use intcode::{load_program, Computer};
+use std::env;
+use std::fs;
+
+const USAGE: &str =
+    "usage: intcode-dump PROGRAM [--input N]... [--fault-on-code-write] [--out FILE]";
+
+// Run a program until it halts, blocks for input or faults, then write a
+// dump of the computer to stdout or FILE. Exits with an error on a fault,
+// after writing the dump.
+fn main() -> Result<(), Error> {
+    let mut path = None;
+    let mut out = None;
+    let mut inputs = vec![];
+    let mut fault_on_code_write = false;
+
+    let mut args = env::args().skip(1);
+    while let Some(arg) = args.next() {
+        let mut value = || args.next().ok_or_else(|| format_err!("{}", USAGE));
+        match arg.as_str() {
+            "--input" => inputs.push(value()?.parse()?),
+            "--out" => out = Some(value()?),
+            "--fault-on-code-write" => fault_on_code_write = true,
+            _ if arg.starts_with("--") || path.is_some() => bail!("{}", USAGE),
+            _ => path = Some(arg),
+        }
+    }
+    let path = path.ok_or_else(|| format_err!("{}", USAGE))?;
+
+    let mut c = Computer::new(load_program(&fs::read_to_string(path)?));
+    c.fault_on_code_write = fault_on_code_write;
+    for input in inputs {
+        c.inject_input(input);
+    }
+    let d: Dump = match dump::run_until_blocked(&mut c) {
+        Ok(()) => dump::dump(&c),
+        Err(d) => *d,
+    };
+
+    match out {
+        Some(out) => fs::write(out, d.to_string())?,
+        None => print!("{}", d),
+    }
+    if let Some(fault) = d.fault {
+        bail!("{}", fault);
+    }
+    Ok(())
+}
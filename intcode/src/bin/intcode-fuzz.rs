@@ -0,0 +1,57 @@
+use failure::{bail, Error};
+use intcode::fuzz::{Case, Fuzzer, Outcome};
+use intcode::load_program;
+use std::collections::hash_map::DefaultHasher;
+use std::env;
+use std::fs;
+use std::hash::{Hash, Hasher};
+use std::panic;
+use std::path::PathBuf;
+
+const USAGE: &str = "usage: intcode-fuzz [--iterations N] [--budget STEPS] [--seed N] \
+                     [--out DIR] [SEED_PROGRAM...]";
+
+fn main() -> Result<(), Error> {
+    let mut iterations = 100_000;
+    let mut budget = 10_000;
+    let mut seed = 0x5eed;
+    let mut out = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("tests/fuzz");
+    let mut seeds = vec![];
+
+    let mut args = env::args().skip(1);
+    while let Some(arg) = args.next() {
+        let mut value = || args.next().ok_or_else(|| failure::format_err!("{}", USAGE));
+        match arg.as_str() {
+            "--iterations" => iterations = value()?.parse()?,
+            "--budget" => budget = value()?.parse()?,
+            "--seed" => seed = value()?.parse()?,
+            "--out" => out = PathBuf::from(value()?),
+            _ if arg.starts_with("--") => bail!("{}", USAGE),
+            _ => seeds.push(Case::new(load_program(&fs::read_to_string(&arg)?), vec![])),
+        }
+    }
+
+    // Crashes are expected and reported below, so don't let the default
+    // hook spam a backtrace for every one of them.
+    panic::set_hook(Box::new(|_| {}));
+
+    let mut fuzzer = Fuzzer::new(seeds, seed, budget);
+    for _ in 0..iterations {
+        if let Some((crash, case)) = fuzzer.iterate() {
+            let regression = case.to_regression(Outcome::Crashed(crash));
+            let mut hasher = DefaultHasher::new();
+            regression.hash(&mut hasher);
+            let path = out.join(format!("{}-{:016x}.txt", crash, hasher.finish()));
+            fs::write(&path, regression)?;
+            println!("{}: {}", crash, path.display());
+        }
+    }
+
+    println!(
+        "executions: {}, corpus: {}, ips covered: {}",
+        fuzzer.executions,
+        fuzzer.corpus_size(),
+        fuzzer.ips_covered()
+    );
+    Ok(())
+}
@@ -0,0 +1,62 @@
+use failure::{bail, format_err, Error};
+use intcode::replay::Replay;
+use intcode::{load_program, Computer};
+use std::env;
+use std::fs;
+use std::io::{self, BufRead};
+
+const USAGE: &str = "usage: intcode-run PROGRAM [--record FILE] [--replay FILE]";
+
+// Run a program interactively, printing each output and reading an integer
+// from stdin whenever it blocks for input. With `--replay`, inputs come from
+// a recording until it runs out. With `--record`, every input read is saved
+// when the program halts or stdin closes, so the session can be replayed.
+fn main() -> Result<(), Error> {
+    let mut path = None;
+    let mut record = None;
+    let mut replay = None;
+
+    let mut args = env::args().skip(1);
+    while let Some(arg) = args.next() {
+        let mut value = || args.next().ok_or_else(|| format_err!("{}", USAGE));
+        match arg.as_str() {
+            "--record" => record = Some(value()?),
+            "--replay" => replay = Some(value()?),
+            _ if arg.starts_with("--") || path.is_some() => bail!("{}", USAGE),
+            _ => path = Some(arg),
+        }
+    }
+    let path = path.ok_or_else(|| format_err!("{}", USAGE))?;
+
+    let mut c = Computer::new(load_program(&fs::read_to_string(path)?));
+    if let Some(replay) = &replay {
+        c.replay(&Replay::parse(&fs::read_to_string(replay)?)?);
+    }
+    if record.is_some() {
+        c.recording = Some(Replay::default());
+    }
+
+    let stdin = io::stdin();
+    let mut lines = stdin.lock().lines();
+    loop {
+        c.run_until_blocked();
+        while let Some(v) = c.read_output() {
+            println!("{}", v);
+        }
+        if c.halted {
+            break;
+        }
+        match lines.next() {
+            Some(line) => c.inject_input(line?.trim().parse()?),
+            None => break,
+        }
+    }
+
+    if let (Some(record), Some(recording)) = (record, &c.recording) {
+        fs::write(record, recording.to_string())?;
+    }
+    if let Some(fault) = c.fault {
+        bail!("{}", fault);
+    }
+    Ok(())
+}
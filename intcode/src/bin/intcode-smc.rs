@@ -0,0 +1,42 @@
+use failure::{bail, format_err, Error};
+use intcode::{load_program, smc, Computer};
+use std::env;
+use std::fs;
+
+const USAGE: &str = "usage: intcode-smc PROGRAM [--fault] [--input N]...";
+
+// Run a program to completion, or until it blocks for input, then print
+// which parts of memory were executed as code and any writes to them. With
+// `--fault`, stop at the first write to code.
+fn main() -> Result<(), Error> {
+    let mut path = None;
+    let mut fault = false;
+    let mut inputs = vec![];
+
+    let mut args = env::args().skip(1);
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--fault" => fault = true,
+            "--input" => {
+                let v = args.next().ok_or_else(|| format_err!("{}", USAGE))?;
+                inputs.push(v.parse()?);
+            }
+            _ if arg.starts_with("--") || path.is_some() => bail!("{}", USAGE),
+            _ => path = Some(arg),
+        }
+    }
+    let path = path.ok_or_else(|| format_err!("{}", USAGE))?;
+
+    let mut c = Computer::new(load_program(&fs::read_to_string(path)?));
+    c.fault_on_code_write = fault;
+    for input in inputs {
+        c.inject_input(input);
+    }
+    c.run_until_blocked();
+
+    print!("{}", smc::summary(&c));
+    if let Some(fault) = c.fault {
+        bail!("{}", fault);
+    }
+    Ok(())
+}
@@ -0,0 +1,392 @@
+use crate::{Computer, Instruction, Int, Opcode, ParamMode};
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::fmt::Write;
+
+// Number of times control passed from one instruction address to the next.
+pub type Profile = HashMap<(usize, usize), u64>;
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub enum Target {
+    Block(usize),
+    Unknown,
+}
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub enum EdgeKind {
+    Fallthrough,
+    Taken,
+    Dynamic,
+}
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub struct Edge {
+    pub from: usize,
+    pub to: Target,
+    pub kind: EdgeKind,
+}
+
+struct Decoded {
+    opcode: Opcode,
+    params: Vec<(ParamMode, Int)>,
+}
+
+impl Decoded {
+    fn len(&self) -> usize {
+        1 + self.params.len()
+    }
+
+    fn is_terminator(&self) -> bool {
+        matches!(
+            self.opcode,
+            Opcode::JumpIfTrue | Opcode::JumpIfFalse | Opcode::Halt
+        )
+    }
+
+    // Static successors of this instruction at `addr`. A jump whose
+    // condition is immediate is either always or never taken, and one whose
+    // target isn't immediate can only be resolved by running the program.
+    fn successors(&self, addr: usize) -> Vec<(Target, EdgeKind)> {
+        let next = (Target::Block(addr + self.len()), EdgeKind::Fallthrough);
+        let (jump_if_true, (cond_mode, cond), (target_mode, target)) = match self.opcode {
+            Opcode::Halt => return vec![],
+            Opcode::JumpIfTrue => (true, self.params[0], self.params[1]),
+            Opcode::JumpIfFalse => (false, self.params[0], self.params[1]),
+            _ => return vec![next],
+        };
+
+        let taken = match target_mode {
+            ParamMode::Immediate if target >= 0 => {
+                (Target::Block(target as usize), EdgeKind::Taken)
+            }
+            _ => (Target::Unknown, EdgeKind::Dynamic),
+        };
+        match cond_mode {
+            ParamMode::Immediate if (cond != 0) == jump_if_true => vec![taken],
+            ParamMode::Immediate => vec![next],
+            _ => vec![next, taken],
+        }
+    }
+
+    fn disassemble(&self) -> String {
+        let p = |i: usize| {
+            let (mode, v) = self.params[i];
+            match mode {
+                ParamMode::Position => format!("[{}]", v),
+                ParamMode::Immediate => format!("{}", v),
+                ParamMode::Relative if v < 0 => format!("[rb{}]", v),
+                ParamMode::Relative => format!("[rb+{}]", v),
+            }
+        };
+        match self.opcode {
+            Opcode::Add => format!("ADD {}, {} -> {}", p(0), p(1), p(2)),
+            Opcode::Mul => format!("MUL {}, {} -> {}", p(0), p(1), p(2)),
+            Opcode::Input => format!("IN -> {}", p(0)),
+            Opcode::Output => format!("OUT {}", p(0)),
+            Opcode::JumpIfTrue => format!("JNZ {}, {}", p(0), p(1)),
+            Opcode::JumpIfFalse => format!("JZ {}, {}", p(0), p(1)),
+            Opcode::LessThan => format!("LT {}, {} -> {}", p(0), p(1), p(2)),
+            Opcode::Equals => format!("EQ {}, {} -> {}", p(0), p(1), p(2)),
+            Opcode::RelativeBase => format!("ARB {}", p(0)),
+            Opcode::Halt => "HALT".to_string(),
+        }
+    }
+}
+
+// Returns `None` for anything that can't be executed: a bad opcode or
+// parameter mode, or an instruction running off the end of the image.
+fn decode(mem: &[Int], addr: usize) -> Option<Decoded> {
+    let inst = Instruction { v: *mem.get(addr)? };
+    let opcode = inst.try_opcode()?;
+    let params = (1..=opcode.num_params())
+        .map(|i| Some((inst.try_param_mode(i as Int)?, *mem.get(addr + i)?)))
+        .collect::<Option<Vec<_>>>()?;
+    Some(Decoded { opcode, params })
+}
+
+pub struct Block {
+    pub start: usize,
+    // Addresses of the instructions in the block. Empty if the block starts
+    // on something that doesn't decode.
+    pub instructions: Vec<usize>,
+}
+
+pub struct Cfg {
+    mem: Vec<Int>,
+    pub blocks: BTreeMap<usize, Block>,
+    pub edges: BTreeSet<Edge>,
+}
+
+impl Cfg {
+    pub fn new(mem: &[Int]) -> Self {
+        Cfg::build(mem, &Profile::new())
+    }
+
+    // As `new`, but also follow the dynamic jumps seen in a profiling run.
+    pub fn with_profile(mem: &[Int], profile: &Profile) -> Self {
+        Cfg::build(mem, profile)
+    }
+
+    fn build(mem: &[Int], profile: &Profile) -> Self {
+        // Dynamic jump targets actually taken, by jump address.
+        let mut resolved: BTreeMap<usize, BTreeSet<usize>> = BTreeMap::new();
+        for (from, to) in profile.keys() {
+            if let Some(d) = decode(mem, *from) {
+                let successors = d.successors(*from);
+                let dynamic = successors.iter().any(|(t, _)| *t == Target::Unknown);
+                let known = successors.iter().any(|(t, _)| *t == Target::Block(*to));
+                if dynamic && !known {
+                    resolved.entry(*from).or_default().insert(*to);
+                }
+            }
+        }
+
+        // Find every statically reachable instruction, and the leaders that
+        // start a new block: the entry point, jump targets, and whatever
+        // follows a jump.
+        let mut leaders = BTreeSet::new();
+        let mut reachable = BTreeSet::new();
+        let mut work = vec![0];
+        leaders.insert(0);
+        while let Some(addr) = work.pop() {
+            if !reachable.insert(addr) {
+                continue;
+            }
+            let d = match decode(mem, addr) {
+                Some(d) => d,
+                None => {
+                    leaders.insert(addr);
+                    continue;
+                }
+            };
+            let mut targets: Vec<usize> = d
+                .successors(addr)
+                .iter()
+                .filter_map(|(t, _)| match t {
+                    Target::Block(a) => Some(*a),
+                    Target::Unknown => None,
+                })
+                .collect();
+            targets.extend(resolved.get(&addr).into_iter().flatten());
+            for t in targets {
+                if d.is_terminator() {
+                    leaders.insert(t);
+                }
+                work.push(t);
+            }
+        }
+
+        let mut blocks = BTreeMap::new();
+        let mut edges = BTreeSet::new();
+        for start in leaders.iter().cloned() {
+            let mut block = Block {
+                start,
+                instructions: vec![],
+            };
+            let mut addr = start;
+            while let Some(d) = decode(mem, addr) {
+                block.instructions.push(addr);
+                let next = addr + d.len();
+                if d.is_terminator() || leaders.contains(&next) {
+                    for (to, kind) in d.successors(addr) {
+                        edges.insert(Edge {
+                            from: start,
+                            to,
+                            kind,
+                        });
+                    }
+                    for to in resolved.get(&addr).into_iter().flatten() {
+                        edges.insert(Edge {
+                            from: start,
+                            to: Target::Block(*to),
+                            kind: EdgeKind::Dynamic,
+                        });
+                    }
+                    break;
+                }
+                addr = next;
+            }
+            blocks.insert(start, block);
+        }
+
+        Cfg {
+            mem: mem.to_vec(),
+            blocks,
+            edges,
+        }
+    }
+
+    // Number of times an edge was followed in a profiling run.
+    pub fn edge_count(&self, edge: &Edge, profile: &Profile) -> u64 {
+        let last = self.blocks[&edge.from].instructions.last();
+        match (last, edge.to) {
+            (Some(from), Target::Block(to)) => *profile.get(&(*from, to)).unwrap_or(&0),
+            _ => 0,
+        }
+    }
+
+    pub fn to_dot(&self, profile: Option<&Profile>) -> String {
+        let mut s = String::new();
+        writeln!(s, "digraph intcode {{").unwrap();
+        writeln!(s, "    node [shape=box, fontname=\"monospace\"];").unwrap();
+
+        for block in self.blocks.values() {
+            let mut label = String::new();
+            for addr in &block.instructions {
+                let d = decode(&self.mem, *addr).unwrap();
+                write!(label, "{}: {}\\l", addr, d.disassemble()).unwrap();
+            }
+            if block.instructions.is_empty() {
+                let v = self.mem.get(block.start).cloned().unwrap_or(0);
+                write!(label, "{}: ?? {}\\l", block.start, v).unwrap();
+            }
+            writeln!(s, "    b{} [label=\"{}\"];", block.start, label).unwrap();
+        }
+
+        if self.edges.iter().any(|e| e.to == Target::Unknown) {
+            writeln!(s, "    unknown [shape=ellipse, label=\"?\"];").unwrap();
+        }
+
+        for edge in &self.edges {
+            let to = match edge.to {
+                Target::Block(a) if self.blocks.contains_key(&a) => format!("b{}", a),
+                Target::Block(_) | Target::Unknown => "unknown".to_string(),
+            };
+            let mut attrs = vec![];
+            let kind = match edge.kind {
+                EdgeKind::Fallthrough => None,
+                EdgeKind::Taken => Some("taken"),
+                EdgeKind::Dynamic => {
+                    attrs.push("style=dashed".to_string());
+                    Some("dynamic")
+                }
+            };
+            match (kind, profile) {
+                (Some(kind), Some(p)) => {
+                    attrs.push(format!("label=\"{} x{}\"", kind, self.edge_count(edge, p)))
+                }
+                (None, Some(p)) => attrs.push(format!("label=\"x{}\"", self.edge_count(edge, p))),
+                (Some(kind), None) => attrs.push(format!("label=\"{}\"", kind)),
+                (None, None) => {}
+            }
+            if let Some(p) = profile {
+                let count = self.edge_count(edge, p);
+                if count == 0 {
+                    attrs.push("color=grey".to_string());
+                } else {
+                    let width = 1.0 + (count as f64).log10();
+                    attrs.push(format!("penwidth={:.1}", width));
+                }
+            }
+            if attrs.is_empty() {
+                writeln!(s, "    b{} -> {};", edge.from, to).unwrap();
+            } else {
+                writeln!(s, "    b{} -> {} [{}];", edge.from, to, attrs.join(", ")).unwrap();
+            }
+        }
+
+        writeln!(s, "}}").unwrap();
+        s
+    }
+}
+
+// Run a computer until it halts or needs more input, recording every
+// transfer of control.
+pub fn profile(c: &mut Computer) -> Profile {
+    let mut profile = Profile::new();
+    while !c.halted && !c.awaiting_input() {
+        let from = c.ip;
+        c.exec_instruction();
+        if !c.halted {
+            *profile.entry((from, c.ip)).or_insert(0) += 1;
+        }
+    }
+    profile
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::load_program;
+
+    const BRANCHY: &str = "3,13,1005,13,10,104,0,1105,1,12,104,1,99,0";
+
+    fn edge(from: usize, to: usize, kind: EdgeKind) -> Edge {
+        Edge {
+            from,
+            to: Target::Block(to),
+            kind,
+        }
+    }
+
+    #[test]
+    fn test_blocks_and_edges() {
+        let cfg = Cfg::new(&load_program(BRANCHY));
+
+        let blocks: Vec<(usize, Vec<usize>)> = cfg
+            .blocks
+            .values()
+            .map(|b| (b.start, b.instructions.clone()))
+            .collect();
+        assert_eq!(
+            blocks,
+            vec![
+                (0, vec![0, 2]),
+                (5, vec![5, 7]),
+                (10, vec![10]),
+                (12, vec![12])
+            ]
+        );
+
+        let edges: Vec<Edge> = cfg.edges.iter().cloned().collect();
+        assert_eq!(
+            edges,
+            vec![
+                edge(0, 5, EdgeKind::Fallthrough),
+                edge(0, 10, EdgeKind::Taken),
+                edge(5, 12, EdgeKind::Taken),
+                edge(10, 12, EdgeKind::Fallthrough),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_dot() {
+        let program = load_program(BRANCHY);
+        let cfg = Cfg::new(&program);
+        let dot = cfg.to_dot(None);
+        assert!(dot.starts_with("digraph intcode {\n"));
+        assert!(dot.contains("    b0 [label=\"0: IN -> [13]\\l2: JNZ [13], 10\\l\"];\n"));
+        assert!(dot.contains("    b0 -> b5;\n"));
+        assert!(dot.contains("    b0 -> b10 [label=\"taken\"];\n"));
+
+        let mut c = Computer::new(program.clone());
+        c.inject_input(1);
+        let p = profile(&mut c);
+        let dot = cfg.to_dot(Some(&p));
+        assert!(dot.contains("    b0 -> b5 [label=\"x0\", color=grey];\n"));
+        assert!(dot.contains("    b0 -> b10 [label=\"taken x1\", penwidth=1.0];\n"));
+    }
+
+    #[test]
+    fn test_dynamic_jumps() {
+        // Jumps to the address held at [rb+0], which is the halt.
+        let program = load_program("109,7,2105,1,0,0,99,6");
+        let cfg = Cfg::new(&program);
+        assert_eq!(cfg.blocks.keys().cloned().collect::<Vec<_>>(), vec![0]);
+        assert!(cfg.edges.contains(&Edge {
+            from: 0,
+            to: Target::Unknown,
+            kind: EdgeKind::Dynamic
+        }));
+
+        let p = profile(&mut Computer::new(program.clone()));
+        let cfg = Cfg::with_profile(&program, &p);
+        assert_eq!(cfg.blocks.keys().cloned().collect::<Vec<_>>(), vec![0, 6]);
+        let dynamic = edge(0, 6, EdgeKind::Dynamic);
+        assert!(cfg.edges.contains(&dynamic));
+        assert_eq!(cfg.edge_count(&dynamic, &p), 1);
+        assert!(cfg
+            .to_dot(None)
+            .contains("    unknown [shape=ellipse, label=\"?\"];\n"));
+    }
+}
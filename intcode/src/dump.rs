@@ -0,0 +1,312 @@
+use crate::fuzz::panic_message;
+use crate::{Computer, Int};
+use failure::{bail, format_err, Error};
+use std::fmt;
+use std::panic::{self, AssertUnwindSafe};
+
+// A snapshot of a computer's state that can be written to a file and read
+// back. Only runs of non-zero cells are written out.
+#[derive(Debug, Clone, Default, Eq, PartialEq)]
+pub struct Dump {
+    pub ip: usize,
+    pub rb: Int,
+    pub halted: bool,
+    pub fault: Option<String>,
+    // Queued inputs not yet read by the program, and outputs not yet read
+    // from it.
+    pub inputs: Vec<Int>,
+    pub outputs: Vec<Int>,
+    pub mem: Vec<Int>,
+}
+
+pub fn dump(c: &Computer) -> Dump {
+    Dump {
+        ip: c.ip,
+        rb: c.rb,
+        halted: c.halted,
+        fault: c.fault.map(|f| f.to_string()),
+        inputs: c.input_queue.iter().cloned().collect(),
+        outputs: c.output_queue.iter().cloned().collect(),
+        mem: c.mem.clone(),
+    }
+}
+
+// Like `Computer::run_until_blocked`, but if the program panics or faults,
+// return a dump of the computer as it was at the offending instruction.
+pub fn run_until_blocked(c: &mut Computer) -> Result<(), Box<Dump>> {
+    let result = panic::catch_unwind(AssertUnwindSafe(|| c.run_until_blocked()));
+    let fault = match result {
+        Ok(()) => c.fault.map(|f| f.to_string()),
+        Err(payload) => Some(panic_message(&*payload)),
+    };
+    match fault {
+        None => Ok(()),
+        Some(fault) => Err(Box::new(Dump {
+            fault: Some(fault),
+            ..dump(c)
+        })),
+    }
+}
+
+fn join(cells: &[Int]) -> String {
+    cells
+        .iter()
+        .map(|v| v.to_string())
+        .collect::<Vec<_>>()
+        .join(",")
+}
+
+fn cells(s: &str) -> Result<Vec<Int>, Error> {
+    s.split(',')
+        .map(|v| v.trim())
+        .filter(|v| !v.is_empty())
+        .map(|v| Ok(v.parse()?))
+        .collect()
+}
+
+// Runs of non-zero cells, as (start, cells).
+fn non_zero_ranges(mem: &[Int]) -> Vec<(usize, &[Int])> {
+    let mut ranges = vec![];
+    let mut start = None;
+    for (addr, v) in mem.iter().enumerate() {
+        match (start, *v != 0) {
+            (None, true) => start = Some(addr),
+            (Some(s), false) => {
+                ranges.push((s, &mem[s..addr]));
+                start = None;
+            }
+            _ => {}
+        }
+    }
+    if let Some(s) = start {
+        ranges.push((s, &mem[s..]));
+    }
+    ranges
+}
+
+impl fmt::Display for Dump {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "ip: {}", self.ip)?;
+        writeln!(f, "rb: {}", self.rb)?;
+        writeln!(f, "halted: {}", self.halted)?;
+        if let Some(fault) = &self.fault {
+            writeln!(f, "fault: {}", fault)?;
+        }
+        writeln!(f, "inputs: {}", join(&self.inputs))?;
+        writeln!(f, "outputs: {}", join(&self.outputs))?;
+        writeln!(f, "size: {}", self.mem.len())?;
+        for (start, cells) in non_zero_ranges(&self.mem) {
+            writeln!(f, "mem {}: {}", start, join(cells))?;
+        }
+        Ok(())
+    }
+}
+
+impl Dump {
+    pub fn parse(s: &str) -> Result<Dump, Error> {
+        let mut d = Dump::default();
+        let mut size = None;
+        let mut ranges = vec![];
+        for line in s.lines() {
+            let (key, value) = match line.find(':') {
+                Some(i) => (&line[..i], line[i + 1..].trim()),
+                None => bail!("Invalid dump line: {}", line),
+            };
+            match key {
+                "ip" => d.ip = value.parse()?,
+                "rb" => d.rb = value.parse()?,
+                "halted" => d.halted = value.parse()?,
+                "fault" => d.fault = Some(value.to_string()),
+                "inputs" => d.inputs = cells(value)?,
+                "outputs" => d.outputs = cells(value)?,
+                "size" => size = Some(value.parse()?),
+                _ if key.starts_with("mem ") => {
+                    ranges.push((key[4..].parse::<usize>()?, cells(value)?));
+                }
+                _ => bail!("Unknown dump key: {}", key),
+            }
+        }
+
+        d.mem = vec![0; size.ok_or_else(|| format_err!("Dump has no size"))?];
+        for (start, cells) in ranges {
+            if start + cells.len() > d.mem.len() {
+                bail!("Memory range at {} is beyond the end of memory", start);
+            }
+            d.mem[start..start + cells.len()].copy_from_slice(&cells);
+        }
+        Ok(d)
+    }
+
+    // Read either a dump or a bare program image, which is treated as a
+    // computer that hasn't started yet.
+    pub fn load(s: &str) -> Result<Dump, Error> {
+        if s.trim_start().starts_with("ip:") {
+            Dump::parse(s)
+        } else {
+            Ok(dump(&Computer::new(cells(s)?)))
+        }
+    }
+}
+
+// A run of consecutive cells that differ between two memories.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Change {
+    pub start: usize,
+    pub before: Vec<Int>,
+    pub after: Vec<Int>,
+}
+
+// Cells past the end of either memory are zero, as they would read.
+pub fn diff_mem(before: &[Int], after: &[Int]) -> Vec<Change> {
+    let cell = |mem: &[Int], addr| *mem.get(addr).unwrap_or(&0);
+    let mut changes: Vec<Change> = vec![];
+    for addr in 0..before.len().max(after.len()) {
+        let (b, a) = (cell(before, addr), cell(after, addr));
+        if a == b {
+            continue;
+        }
+        match changes.last_mut() {
+            Some(c) if c.start + c.before.len() == addr => {
+                c.before.push(b);
+                c.after.push(a);
+            }
+            _ => changes.push(Change {
+                start: addr,
+                before: vec![b],
+                after: vec![a],
+            }),
+        }
+    }
+    changes
+}
+
+pub fn diff(before: &Dump, after: &Dump) -> String {
+    let mut s = String::new();
+    let mut field = |name: &str, b: String, a: String| {
+        if a != b {
+            s += &format!("{}: {} -> {}\n", name, b, a);
+        }
+    };
+    field("ip", before.ip.to_string(), after.ip.to_string());
+    field("rb", before.rb.to_string(), after.rb.to_string());
+    field(
+        "halted",
+        before.halted.to_string(),
+        after.halted.to_string(),
+    );
+    let fault = |d: &Dump| d.fault.clone().unwrap_or_else(|| "none".to_string());
+    field("fault", fault(before), fault(after));
+    field("inputs", join(&before.inputs), join(&after.inputs));
+    field("outputs", join(&before.outputs), join(&after.outputs));
+
+    for c in diff_mem(&before.mem, &after.mem) {
+        s += &format!(
+            "mem {}..{}: {} -> {}\n",
+            c.start,
+            c.start + c.before.len(),
+            join(&c.before),
+            join(&c.after)
+        );
+    }
+    s
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::load_program;
+
+    #[test]
+    fn test_round_trip() {
+        let mut c = Computer::new(load_program("3,9,104,5,3,10,99,0,0,0,7"));
+        c.inject_input(4);
+        c.run_until_blocked();
+        c.inject_input(6);
+        let d = dump(&c);
+
+        assert_eq!(
+            d.to_string(),
+            "ip: 4\n\
+             rb: 0\n\
+             halted: false\n\
+             inputs: 6\n\
+             outputs: 5\n\
+             size: 11\n\
+             mem 0: 3,9,104,5,3,10,99\n\
+             mem 9: 4,7\n"
+        );
+        assert_eq!(Dump::parse(&d.to_string()).unwrap(), d);
+    }
+
+    #[test]
+    fn test_run_until_blocked_dumps_faults() {
+        let mut c = Computer::new(load_program("1101,1,2,5,77,0,99"));
+        let d = run_until_blocked(&mut c).unwrap_err();
+        assert_eq!(d.ip, 4);
+        assert_eq!(d.mem[5], 3);
+        assert_eq!(d.fault, Some("Invalid opcode: 77".to_string()));
+
+        let mut c = Computer::new(load_program("1101,1,2,1,99"));
+        c.fault_on_code_write = true;
+        let d = run_until_blocked(&mut c).unwrap_err();
+        assert_eq!(d.ip, 0);
+        assert_eq!(
+            d.fault,
+            Some("write to code: ip 0 wrote 3 to 1 (was 1)".to_string())
+        );
+
+        let mut c = Computer::new(load_program("99"));
+        assert!(run_until_blocked(&mut c).is_ok());
+    }
+
+    #[test]
+    fn test_diff_mem() {
+        let test_data = [
+            (vec![1, 2, 3], vec![1, 2, 3], vec![]),
+            (
+                vec![1, 2, 3, 4, 5],
+                vec![1, 9, 9, 4, 9],
+                vec![
+                    Change {
+                        start: 1,
+                        before: vec![2, 3],
+                        after: vec![9, 9],
+                    },
+                    Change {
+                        start: 4,
+                        before: vec![5],
+                        after: vec![9],
+                    },
+                ],
+            ),
+            (
+                vec![1],
+                vec![1, 0, 0, 5],
+                vec![Change {
+                    start: 3,
+                    before: vec![0],
+                    after: vec![5],
+                }],
+            ),
+        ];
+
+        for (before, after, expected) in &test_data {
+            assert_eq!(diff_mem(before, after), *expected);
+        }
+    }
+
+    #[test]
+    fn test_diff_against_image() {
+        let image = "1,0,0,0,2,5,5,9,99,0";
+        let mut c = Computer::new(load_program(image));
+        c.run();
+
+        assert_eq!(
+            diff(&Dump::load(image).unwrap(), &dump(&c)),
+            "ip: 0 -> 8\n\
+             halted: false -> true\n\
+             mem 0..1: 1 -> 2\n\
+             mem 9..10: 0 -> 25\n"
+        );
+    }
+}
@@ -0,0 +1,270 @@
+use crate::{Computer, Int};
+use common::grid::Point;
+use std::collections::hash_map::Entry;
+use std::collections::{HashMap, VecDeque};
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum Move {
+    North,
+    South,
+    West,
+    East,
+}
+
+const MOVES: [Move; 4] = [Move::North, Move::South, Move::West, Move::East];
+
+impl Move {
+    fn command(self) -> Int {
+        match self {
+            Move::North => 1,
+            Move::South => 2,
+            Move::West => 3,
+            Move::East => 4,
+        }
+    }
+
+    fn step(self, p: Point) -> Point {
+        match self {
+            Move::North => Point::new(p.x, p.y + 1),
+            Move::South => Point::new(p.x, p.y - 1),
+            Move::West => Point::new(p.x - 1, p.y),
+            Move::East => Point::new(p.x + 1, p.y),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum Status {
+    Wall,
+    Moved,
+    Found,
+}
+
+impl Status {
+    fn from_int(v: Int) -> Status {
+        match v {
+            0 => Status::Wall,
+            1 => Status::Moved,
+            2 => Status::Found,
+            _ => panic!("Invalid status: {}", v),
+        }
+    }
+}
+
+// Anything that can be sent movement commands and replies with a status.
+// Cloning a droid must snapshot its whole state, so that exploration can
+// carry on from any branch point without walking back to it.
+pub trait Droid: Clone {
+    fn command(&mut self, m: Move) -> Status;
+}
+
+impl Droid for Computer {
+    fn command(&mut self, m: Move) -> Status {
+        self.inject_input(m.command());
+        self.run_until_blocked();
+        let status = self
+            .read_output()
+            .unwrap_or_else(|| panic!("No status reply to {:?}", m));
+        Status::from_int(status)
+    }
+}
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum Tile {
+    Wall,
+    Open,
+    Target,
+}
+
+#[derive(Default)]
+pub struct Area {
+    tiles: HashMap<Point, Tile>,
+}
+
+impl Area {
+    pub fn tile(&self, p: Point) -> Option<Tile> {
+        self.tiles.get(&p).cloned()
+    }
+
+    pub fn target(&self) -> Option<Point> {
+        self.tiles
+            .iter()
+            .find(|(_, t)| **t == Tile::Target)
+            .map(|(p, _)| *p)
+    }
+
+    // Breadth-first distance from `from` to every reachable non-wall tile.
+    pub fn distances(&self, from: Point) -> HashMap<Point, usize> {
+        let mut distances = HashMap::new();
+        let mut queue = VecDeque::new();
+        distances.insert(from, 0);
+        queue.push_back(from);
+
+        while let Some(p) = queue.pop_front() {
+            let d = distances[&p];
+            for m in &MOVES {
+                let next = m.step(p);
+                match self.tile(next) {
+                    Some(Tile::Open) | Some(Tile::Target) => {}
+                    _ => continue,
+                }
+                if let Entry::Vacant(e) = distances.entry(next) {
+                    e.insert(d + 1);
+                    queue.push_back(next);
+                }
+            }
+        }
+        distances
+    }
+
+    pub fn shortest_path(&self, from: Point, to: Point) -> Option<usize> {
+        self.distances(from).get(&to).cloned()
+    }
+
+    // Time for something spreading one tile per step from `from` to fill
+    // every reachable tile.
+    pub fn fill_time(&self, from: Point) -> usize {
+        self.distances(from).values().cloned().max().unwrap_or(0)
+    }
+
+    pub fn render(&self) -> String {
+        let mut s = String::new();
+        if self.tiles.is_empty() {
+            return s;
+        }
+        let min_x = self.tiles.keys().map(|p| p.x).min().unwrap();
+        let max_x = self.tiles.keys().map(|p| p.x).max().unwrap();
+        let min_y = self.tiles.keys().map(|p| p.y).min().unwrap();
+        let max_y = self.tiles.keys().map(|p| p.y).max().unwrap();
+
+        for y in (min_y..=max_y).rev() {
+            for x in min_x..=max_x {
+                s += match self.tile(Point::new(x, y)) {
+                    None => " ",
+                    Some(Tile::Wall) => "#",
+                    Some(Tile::Open) => ".",
+                    Some(Tile::Target) => "O",
+                }
+            }
+            s += "\n";
+        }
+        s
+    }
+}
+
+// Map everything reachable from the droid's starting position, which is
+// taken to be the origin. Each probe runs on a clone of the droid at the
+// branch point, so backtracking is just dropping the clone.
+pub fn explore<D: Droid>(droid: D) -> Area {
+    let mut area = Area::default();
+    let origin = Point::new(0, 0);
+    area.tiles.insert(origin, Tile::Open);
+
+    let mut stack = vec![(origin, droid)];
+    while let Some((p, droid)) = stack.pop() {
+        for m in &MOVES {
+            let next = m.step(p);
+            if area.tiles.contains_key(&next) {
+                continue;
+            }
+            let mut probe = droid.clone();
+            let tile = match probe.command(*m) {
+                Status::Wall => Tile::Wall,
+                Status::Moved => Tile::Open,
+                Status::Found => Tile::Target,
+            };
+            area.tiles.insert(next, tile);
+            if tile != Tile::Wall {
+                stack.push((next, probe));
+            }
+        }
+    }
+    area
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::load_program;
+
+    // A droid that walks a fixed map, with `D` marking its starting position
+    // and `O` the target.
+    #[derive(Clone)]
+    struct MapDroid {
+        map: Vec<Vec<char>>,
+        pos: (usize, usize),
+    }
+
+    impl MapDroid {
+        fn new(map: &str) -> Self {
+            let map: Vec<Vec<char>> = map.lines().map(|l| l.chars().collect()).collect();
+            let pos = map
+                .iter()
+                .enumerate()
+                .find_map(|(row, l)| l.iter().position(|c| *c == 'D').map(|col| (row, col)))
+                .unwrap();
+            MapDroid { map, pos }
+        }
+    }
+
+    impl Droid for MapDroid {
+        fn command(&mut self, m: Move) -> Status {
+            let (row, col) = self.pos;
+            let next = match m {
+                Move::North => (row - 1, col),
+                Move::South => (row + 1, col),
+                Move::West => (row, col - 1),
+                Move::East => (row, col + 1),
+            };
+            match self.map[next.0][next.1] {
+                '#' => Status::Wall,
+                c => {
+                    self.pos = next;
+                    if c == 'O' {
+                        Status::Found
+                    } else {
+                        Status::Moved
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_explore() {
+        let map = " ##   \n\
+                   #..## \n\
+                   #.#..#\n\
+                   #DO.# \n\
+                   \x20###  ";
+        let area = explore(MapDroid::new(map));
+
+        assert_eq!(area.target(), Some(Point::new(1, 0)));
+        assert_eq!(
+            area.shortest_path(Point::new(0, 0), Point::new(1, 0)),
+            Some(1)
+        );
+        assert_eq!(area.fill_time(Point::new(1, 0)), 4);
+        assert_eq!(
+            area.render(),
+            " ##   \n\
+             #..## \n\
+             #.#..#\n\
+             #.O.# \n\
+             \x20###  \n"
+        );
+    }
+
+    #[test]
+    fn test_explore_computer() {
+        // Replies "wall" to every movement command.
+        let computer = Computer::new(load_program("3,100,104,0,1105,1,0"));
+        let area = explore(computer);
+
+        assert_eq!(area.target(), None);
+        assert_eq!(area.tile(Point::new(0, 0)), Some(Tile::Open));
+        assert_eq!(area.tile(Point::new(0, 1)), Some(Tile::Wall));
+        assert_eq!(area.fill_time(Point::new(0, 0)), 0);
+        assert_eq!(area.render(), " # \n#.#\n # \n");
+    }
+}
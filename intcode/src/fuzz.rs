@@ -0,0 +1,485 @@
+use crate::{Computer, Int};
+use common::random::Random;
+use failure::{bail, format_err, Error};
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+use std::panic::{self, AssertUnwindSafe};
+
+#[derive(Debug, Clone, Default, Eq, PartialEq, Hash)]
+pub struct Case {
+    pub program: Vec<Int>,
+    pub inputs: Vec<Int>,
+}
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+pub enum Crash {
+    InvalidOpcode,
+    InvalidParamMode,
+    InvalidAddress,
+    ImmediateWrite,
+    Overflow,
+    Hang,
+    Other,
+}
+
+const CRASHES: [Crash; 7] = [
+    Crash::InvalidOpcode,
+    Crash::InvalidParamMode,
+    Crash::InvalidAddress,
+    Crash::ImmediateWrite,
+    Crash::Overflow,
+    Crash::Hang,
+    Crash::Other,
+];
+
+impl Crash {
+    fn from_panic(msg: &str) -> Crash {
+        if msg.starts_with("Invalid opcode") {
+            Crash::InvalidOpcode
+        } else if msg.starts_with("Invalid parameter mode") {
+            Crash::InvalidParamMode
+        } else if msg.starts_with("Invalid address") || msg.contains("out of bounds") {
+            Crash::InvalidAddress
+        } else if msg.starts_with("Immediate mode not supported") {
+            Crash::ImmediateWrite
+        } else if msg.contains("overflow") {
+            Crash::Overflow
+        } else {
+            Crash::Other
+        }
+    }
+
+    fn name(self) -> &'static str {
+        match self {
+            Crash::InvalidOpcode => "invalid-opcode",
+            Crash::InvalidParamMode => "invalid-param-mode",
+            Crash::InvalidAddress => "invalid-address",
+            Crash::ImmediateWrite => "immediate-write",
+            Crash::Overflow => "overflow",
+            Crash::Hang => "hang",
+            Crash::Other => "other",
+        }
+    }
+}
+
+impl fmt::Display for Crash {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+pub enum Outcome {
+    Halted,
+    AwaitingInput,
+    Crashed(Crash),
+}
+
+impl fmt::Display for Outcome {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Outcome::Halted => write!(f, "halted"),
+            Outcome::AwaitingInput => write!(f, "awaiting-input"),
+            Outcome::Crashed(crash) => write!(f, "{}", crash),
+        }
+    }
+}
+
+impl Outcome {
+    fn parse(s: &str) -> Result<Outcome, Error> {
+        match s {
+            "halted" => Ok(Outcome::Halted),
+            "awaiting-input" => Ok(Outcome::AwaitingInput),
+            _ => CRASHES
+                .iter()
+                .find(|c| c.name() == s)
+                .map(|c| Outcome::Crashed(*c))
+                .ok_or_else(|| format_err!("Unknown outcome: {}", s)),
+        }
+    }
+}
+
+// Number of times each instruction address was executed.
+pub type Coverage = HashMap<usize, u64>;
+
+pub(crate) fn panic_message(payload: &(dyn std::any::Any + Send)) -> String {
+    if let Some(s) = payload.downcast_ref::<&str>() {
+        s.to_string()
+    } else if let Some(s) = payload.downcast_ref::<String>() {
+        s.clone()
+    } else {
+        String::new()
+    }
+}
+
+// Run a case for at most `budget` instructions. Panics inside the computer
+// are caught and classified rather than propagated.
+pub fn execute(case: &Case, budget: usize) -> (Outcome, Coverage) {
+    let mut coverage = Coverage::new();
+    let result = panic::catch_unwind(AssertUnwindSafe(|| {
+        let mut c = Computer::new(case.program.clone());
+        for input in &case.inputs {
+            c.inject_input(*input);
+        }
+        for _ in 0..budget {
+            if c.halted {
+                return Outcome::Halted;
+            }
+            if c.awaiting_input() {
+                return Outcome::AwaitingInput;
+            }
+            *coverage.entry(c.ip).or_insert(0) += 1;
+            c.exec_instruction();
+        }
+        if c.halted {
+            Outcome::Halted
+        } else {
+            Outcome::Crashed(Crash::Hang)
+        }
+    }));
+
+    let outcome = match result {
+        Ok(outcome) => outcome,
+        Err(payload) => Outcome::Crashed(Crash::from_panic(&panic_message(&*payload))),
+    };
+    (outcome, coverage)
+}
+
+// Hit counts are bucketed by powers of two, so that a loop running a few
+// more times than before doesn't count as new behaviour.
+fn features(coverage: &Coverage) -> impl Iterator<Item = (usize, u32)> + '_ {
+    coverage
+        .iter()
+        .map(|(ip, hits)| (*ip, 64 - hits.leading_zeros()))
+}
+
+// An index below n, for picking mutations.
+fn below(rng: &mut Random, n: usize) -> usize {
+    rng.below(n as u64) as usize
+}
+
+fn interesting_value(rng: &mut Random, len: usize) -> Int {
+    match below(rng, 6) {
+        0 => [0, 1, -1, 99, Int::MAX, Int::MIN][below(rng, 6)],
+        1 => below(rng, len + 4) as Int,
+        2 => {
+            let opcode = [1, 2, 3, 4, 5, 6, 7, 8, 9, 99][below(rng, 10)];
+            let modes = (0..3).fold(0, |m, _| m * 10 + below(rng, 3) as Int);
+            modes * 100 + opcode
+        }
+        3 => below(rng, 20) as Int - 10,
+        4 => rng.next_u64() as Int,
+        _ => below(rng, 100_000) as Int,
+    }
+}
+
+fn mutate_cells(cells: &mut Vec<Int>, rng: &mut Random, len: usize) {
+    match below(rng, 4) {
+        0 if !cells.is_empty() => {
+            let i = below(rng, cells.len());
+            cells[i] = interesting_value(rng, len);
+        }
+        1 => {
+            let i = below(rng, cells.len() + 1);
+            cells.insert(i, interesting_value(rng, len));
+        }
+        2 if !cells.is_empty() => {
+            cells.remove(below(rng, cells.len()));
+        }
+        3 if !cells.is_empty() => {
+            let i = below(rng, cells.len());
+            cells[i] = cells[i].wrapping_add(below(rng, 3) as Int - 1);
+        }
+        _ => cells.push(interesting_value(rng, len)),
+    }
+}
+
+impl Case {
+    pub fn new(program: Vec<Int>, inputs: Vec<Int>) -> Self {
+        Case { program, inputs }
+    }
+
+    // Decode raw fuzzer bytes: the first byte is the number of queued
+    // inputs, then every pair of bytes is one little-endian `i16` cell.
+    pub fn from_bytes(data: &[u8]) -> Self {
+        let num_inputs = data.first().map_or(0, |n| (n % 8) as usize);
+        let cells: Vec<Int> = data
+            .get(1..)
+            .unwrap_or(&[])
+            .chunks_exact(2)
+            .map(|b| Int::from(i16::from_le_bytes([b[0], b[1]])))
+            .collect();
+        let split = num_inputs.min(cells.len());
+        Case {
+            inputs: cells[..split].to_vec(),
+            program: cells[split..].to_vec(),
+        }
+    }
+
+    fn mutate(&mut self, rng: &mut Random) {
+        let len = self.program.len();
+        for _ in 0..=below(rng, 4) {
+            if below(rng, 4) == 0 {
+                mutate_cells(&mut self.inputs, rng, len);
+            } else {
+                mutate_cells(&mut self.program, rng, len);
+            }
+        }
+    }
+
+    pub fn to_regression(&self, outcome: Outcome) -> String {
+        let join = |cells: &[Int]| {
+            cells
+                .iter()
+                .map(Int::to_string)
+                .collect::<Vec<String>>()
+                .join(",")
+        };
+        format!(
+            "outcome: {}\nprogram: {}\ninputs: {}\n",
+            outcome,
+            join(&self.program),
+            join(&self.inputs)
+        )
+    }
+
+    pub fn parse_regression(s: &str) -> Result<(Case, Outcome), Error> {
+        let mut outcome = None;
+        let mut case = Case::default();
+        for line in s.lines() {
+            let (key, value) = match line.find(':') {
+                Some(i) => (&line[..i], line[i + 1..].trim()),
+                None => bail!("Invalid regression line: {}", line),
+            };
+            let cells = || -> Result<Vec<Int>, Error> {
+                value
+                    .split(',')
+                    .filter(|v| !v.is_empty())
+                    .map(|v| Ok(v.parse()?))
+                    .collect()
+            };
+            match key {
+                "outcome" => outcome = Some(Outcome::parse(value)?),
+                "program" => case.program = cells()?,
+                "inputs" => case.inputs = cells()?,
+                _ => bail!("Unknown regression key: {}", key),
+            }
+        }
+        let outcome = outcome.ok_or_else(|| format_err!("Regression has no outcome"))?;
+        Ok((case, outcome))
+    }
+}
+
+fn program_cells(c: &mut Case) -> &mut Vec<Int> {
+    &mut c.program
+}
+
+fn input_cells(c: &mut Case) -> &mut Vec<Int> {
+    &mut c.inputs
+}
+
+// Shrink a case while it still produces the same outcome: drop chunks of
+// cells, halving the chunk size each pass, then trim leading digits off
+// whatever cells remain.
+pub fn minimise(case: &Case, budget: usize) -> Case {
+    let (target, _) = execute(case, budget);
+    let reproduces = |c: &Case| execute(c, budget).0 == target;
+    let mut best = case.clone();
+
+    // Dropping inputs can make more of the program removable and vice
+    // versa, so keep going until neither shrinks any further.
+    loop {
+        let before = best.clone();
+        for cells in &[program_cells, input_cells] {
+            let mut chunk = cells(&mut best).len();
+            while chunk > 0 {
+                let mut i = 0;
+                while i < cells(&mut best).len() {
+                    let mut candidate = best.clone();
+                    let v = cells(&mut candidate);
+                    let end = (i + chunk).min(v.len());
+                    v.drain(i..end);
+                    if reproduces(&candidate) {
+                        best = candidate;
+                    } else {
+                        i += chunk;
+                    }
+                }
+                chunk /= 2;
+            }
+            for i in 0..cells(&mut best).len() {
+                let v = cells(&mut best)[i];
+                let simpler = (0..19)
+                    .map(|k| v % 10_i64.pow(k))
+                    .filter(|s| s.abs() < v.abs())
+                    .find(|s| {
+                        let mut candidate = best.clone();
+                        cells(&mut candidate)[i] = *s;
+                        reproduces(&candidate)
+                    });
+                if let Some(s) = simpler {
+                    cells(&mut best)[i] = s;
+                }
+            }
+        }
+        if best == before {
+            break;
+        }
+    }
+    best
+}
+
+pub struct Fuzzer {
+    corpus: Vec<Case>,
+    seen: HashSet<(usize, u32)>,
+    crashes: HashSet<Crash>,
+    rng: Random,
+    budget: usize,
+    pub executions: u64,
+}
+
+impl Fuzzer {
+    pub fn new(seeds: Vec<Case>, seed: u64, budget: usize) -> Self {
+        let mut fuzzer = Fuzzer {
+            corpus: vec![],
+            seen: HashSet::new(),
+            crashes: HashSet::new(),
+            rng: Random::new(seed),
+            budget,
+            executions: 0,
+        };
+        for case in seeds {
+            fuzzer.try_case(case);
+        }
+        if fuzzer.corpus.is_empty() {
+            fuzzer.corpus.push(Case::new(vec![99], vec![]));
+        }
+        fuzzer
+    }
+
+    pub fn corpus_size(&self) -> usize {
+        self.corpus.len()
+    }
+
+    // Number of distinct instruction addresses any case has executed.
+    pub fn ips_covered(&self) -> usize {
+        self.seen
+            .iter()
+            .map(|(ip, _)| ip)
+            .collect::<HashSet<_>>()
+            .len()
+    }
+
+    fn try_case(&mut self, case: Case) -> Option<(Crash, Case)> {
+        self.executions += 1;
+        let (outcome, coverage) = execute(&case, self.budget);
+        let mut new_coverage = false;
+        for feature in features(&coverage) {
+            new_coverage |= self.seen.insert(feature);
+        }
+
+        if let Outcome::Crashed(crash) = outcome {
+            if self.crashes.insert(crash) {
+                return Some((crash, minimise(&case, self.budget)));
+            }
+        } else if new_coverage {
+            self.corpus.push(case);
+        }
+        None
+    }
+
+    // Mutate a case from the corpus and run it, returning the minimised
+    // case if it crashed in a way that hasn't been seen before.
+    pub fn iterate(&mut self) -> Option<(Crash, Case)> {
+        let mut case = self.corpus[below(&mut self.rng, self.corpus.len())].clone();
+        case.mutate(&mut self.rng);
+        self.try_case(case)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::load_program;
+
+    #[test]
+    fn test_execute() {
+        let test_data = [
+            ("1101,1,2,5,99", vec![], Outcome::Halted),
+            ("3,0,99", vec![], Outcome::AwaitingInput),
+            ("3,0,99", vec![1], Outcome::Halted),
+            ("42", vec![], Outcome::Crashed(Crash::InvalidOpcode)),
+            (
+                "301,0,0,0,99",
+                vec![],
+                Outcome::Crashed(Crash::InvalidParamMode),
+            ),
+            (
+                "1,-1,0,0,99",
+                vec![],
+                Outcome::Crashed(Crash::InvalidAddress),
+            ),
+            (
+                "11101,0,0,0,99",
+                vec![],
+                Outcome::Crashed(Crash::ImmediateWrite),
+            ),
+            (
+                "1102,4611686018427387904,2,0,99",
+                vec![],
+                Outcome::Crashed(Crash::Overflow),
+            ),
+            (
+                "109,9223372036854775807,109,1,99",
+                vec![],
+                Outcome::Crashed(Crash::Overflow),
+            ),
+            ("1105,1,0", vec![], Outcome::Crashed(Crash::Hang)),
+        ];
+
+        for (code, inputs, expected) in &test_data {
+            let case = Case::new(load_program(code), inputs.clone());
+            assert_eq!(execute(&case, 1000).0, *expected);
+        }
+    }
+
+    #[test]
+    fn test_coverage() {
+        let case = Case::new(load_program("1101,0,0,0,1105,1,9,1,1,99"), vec![]);
+        let (outcome, coverage) = execute(&case, 1000);
+        assert_eq!(outcome, Outcome::Halted);
+        let mut ips: Vec<usize> = coverage.keys().cloned().collect();
+        ips.sort();
+        assert_eq!(ips, vec![0, 4, 9]);
+    }
+
+    #[test]
+    fn test_minimise() {
+        let case = Case::new(
+            load_program("1101,1,2,20,1002,20,3,21,1,-1,0,0,99"),
+            vec![4, 5],
+        );
+        let minimal = minimise(&case, 1000);
+        assert_eq!(
+            execute(&minimal, 1000).0,
+            Outcome::Crashed(Crash::InvalidAddress)
+        );
+        assert_eq!(minimal, Case::new(vec![1, -1], vec![]));
+    }
+
+    #[test]
+    fn test_regression_round_trip() {
+        let case = Case::new(vec![3, 0, 99], vec![-7, 8]);
+        let outcome = Outcome::Crashed(Crash::Hang);
+        let s = case.to_regression(outcome);
+        assert_eq!(s, "outcome: hang\nprogram: 3,0,99\ninputs: -7,8\n");
+        assert_eq!(Case::parse_regression(&s).unwrap(), (case, outcome));
+    }
+
+    #[test]
+    fn test_from_bytes() {
+        let case = Case::from_bytes(&[1, 5, 0, 0xff, 0xff, 99, 0]);
+        assert_eq!(case, Case::new(vec![-1, 99], vec![5]));
+        assert_eq!(Case::from_bytes(&[]), Case::default());
+    }
+}
@@ -0,0 +1,478 @@
+use common::parse::parse_list;
+use replay::{InputRead, Replay};
+use smc::CodeWrite;
+use std::collections::VecDeque;
+use std::fmt;
+
+pub mod cfg;
+pub mod dump;
+pub mod explore;
+pub mod fuzz;
+pub mod replay;
+pub mod robot;
+pub mod smc;
+
+pub type Int = i64;
+
+// Memory grows on demand up to this size, so that cloning a computer only
+// copies the cells the program has actually touched.
+const MEM_SIZE: usize = 1_000_000;
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum Opcode {
+    Add,
+    Mul,
+    Input,
+    Output,
+    JumpIfTrue,
+    JumpIfFalse,
+    LessThan,
+    Equals,
+    RelativeBase,
+    Halt,
+}
+
+impl Opcode {
+    pub fn num_params(self) -> usize {
+        use Opcode::*;
+        match self {
+            Add | Mul | LessThan | Equals => 3,
+            JumpIfTrue | JumpIfFalse => 2,
+            Input | Output | RelativeBase => 1,
+            Halt => 0,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum ParamMode {
+    Position,
+    Immediate,
+    Relative,
+}
+
+// Why a computer stopped without reaching a halt instruction. The
+// instruction pointer is left on the faulting instruction.
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum Fault {
+    WriteToCode(CodeWrite),
+    // An input was read at a different step from the one in the replay.
+    ReplayDiverged { step: u64, expected: u64 },
+}
+
+impl fmt::Display for Fault {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Fault::WriteToCode(w) => write!(f, "write to code: {}", w),
+            Fault::ReplayDiverged { step, expected } => write!(
+                f,
+                "replay diverged: input read at step {} but recorded at step {}",
+                step, expected
+            ),
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct Computer {
+    pub ip: usize, // instruction pointer
+    pub rb: Int,   // relative base
+    pub mem: Vec<Int>,
+    pub halted: bool,
+    pub last_output: Int,
+    pub fault: Option<Fault>,
+    // Every write by the program to a cell that had already been executed.
+    pub code_writes: Vec<CodeWrite>,
+    pub fault_on_code_write: bool,
+    // Instructions completed so far. Spinning on an input with nothing to
+    // read doesn't count.
+    pub steps: u64,
+    // Every input read, if recording.
+    pub recording: Option<Replay>,
+    replaying: VecDeque<InputRead>,
+    input_queue: VecDeque<Int>,
+    output_queue: VecDeque<Int>,
+    // Cells that have been part of an executed instruction, opcode or
+    // parameter.
+    executed: Vec<bool>,
+}
+
+pub struct Instruction {
+    pub v: Int,
+}
+
+impl Instruction {
+    pub fn try_opcode(&self) -> Option<Opcode> {
+        use Opcode::*;
+        match self.v % 100 {
+            1 => Some(Add),
+            2 => Some(Mul),
+            3 => Some(Input),
+            4 => Some(Output),
+            5 => Some(JumpIfTrue),
+            6 => Some(JumpIfFalse),
+            7 => Some(LessThan),
+            8 => Some(Equals),
+            9 => Some(RelativeBase),
+            99 => Some(Halt),
+            _ => None,
+        }
+    }
+
+    pub fn opcode(&self) -> Opcode {
+        self.try_opcode()
+            .unwrap_or_else(|| panic!("Invalid opcode: {}", self.v))
+    }
+
+    pub fn try_param_mode(&self, param_index: Int) -> Option<ParamMode> {
+        use ParamMode::*;
+        match self.v / 10_i64.pow((param_index + 1) as u32) % 10 {
+            0 => Some(Position),
+            1 => Some(Immediate),
+            2 => Some(Relative),
+            _ => None,
+        }
+    }
+
+    pub fn param_mode(&self, param_index: Int) -> ParamMode {
+        self.try_param_mode(param_index).unwrap_or_else(|| {
+            panic!(
+                "Invalid parameter mode. v:{} param_index:{}",
+                self.v, param_index
+            )
+        })
+    }
+}
+
+// Arithmetic panics on overflow in every build profile, rather than only when
+// overflow checks are on, so a program behaves the same however it's built.
+fn add(a: Int, b: Int) -> Int {
+    a.checked_add(b)
+        .unwrap_or_else(|| panic!("Arithmetic overflow: {} + {}", a, b))
+}
+
+fn mul(a: Int, b: Int) -> Int {
+    a.checked_mul(b)
+        .unwrap_or_else(|| panic!("Arithmetic overflow: {} * {}", a, b))
+}
+
+impl Computer {
+    pub fn new(mem: Vec<Int>) -> Self {
+        Computer {
+            ip: 0,
+            rb: 0,
+            mem,
+            halted: false,
+            last_output: 0,
+            fault: None,
+            code_writes: vec![],
+            fault_on_code_write: false,
+            steps: 0,
+            recording: None,
+            replaying: VecDeque::new(),
+            input_queue: VecDeque::new(),
+            output_queue: VecDeque::new(),
+            executed: vec![],
+        }
+    }
+
+    fn address(v: Int) -> usize {
+        if v < 0 || v as usize >= MEM_SIZE {
+            panic!("Invalid address: {}", v);
+        }
+        v as usize
+    }
+
+    pub fn read_mem(&self, addr: usize) -> Int {
+        *self.mem.get(addr).unwrap_or(&0)
+    }
+
+    pub fn write_mem(&mut self, addr: usize, v: Int) {
+        if addr >= self.mem.len() {
+            self.mem.resize(addr + 1, 0);
+        }
+        self.mem[addr] = v;
+    }
+
+    pub fn executed(&self, addr: usize) -> bool {
+        *self.executed.get(addr).unwrap_or(&false)
+    }
+
+    fn mark_executed(&mut self, len: usize) {
+        if self.ip + len > self.executed.len() {
+            self.executed.resize(self.ip + len, false);
+        }
+        for cell in &mut self.executed[self.ip..self.ip + len] {
+            *cell = true;
+        }
+    }
+
+    pub fn instruction(&self) -> Instruction {
+        Instruction {
+            v: self.read_mem(self.ip),
+        }
+    }
+
+    fn raw_param(&self, i: Int) -> Int {
+        self.read_mem(self.ip + i as usize)
+    }
+
+    fn read_param(&self, inst: &Instruction, i: Int) -> Int {
+        match inst.param_mode(i) {
+            ParamMode::Immediate => self.raw_param(i),
+            ParamMode::Position => self.read_mem(Self::address(self.raw_param(i))),
+            ParamMode::Relative => self.read_mem(Self::address(add(self.rb, self.raw_param(i)))),
+        }
+    }
+
+    fn write_param(&mut self, inst: &Instruction, i: Int, v: Int) {
+        let addr = match inst.param_mode(i) {
+            ParamMode::Immediate => panic!("Immediate mode not supported for writes!"),
+            ParamMode::Position => Self::address(self.raw_param(i)),
+            ParamMode::Relative => Self::address(add(self.rb, self.raw_param(i))),
+        };
+        if self.executed(addr) {
+            let write = CodeWrite {
+                ip: self.ip,
+                addr,
+                old: self.read_mem(addr),
+                new: v,
+            };
+            self.code_writes.push(write);
+            if self.fault_on_code_write {
+                self.fault = Some(Fault::WriteToCode(write));
+                self.halted = true;
+                return;
+            }
+        }
+        self.write_mem(addr, v);
+    }
+
+    pub fn inject_input(&mut self, v: Int) {
+        self.input_queue.push_back(v);
+    }
+
+    // Feed the inputs from a recording back in, at the steps they were
+    // read. Anything injected is only read once the replay runs out.
+    pub fn replay(&mut self, replay: &Replay) {
+        self.replaying.extend(replay.reads.iter().cloned());
+    }
+
+    fn read_input(&mut self) -> Option<Int> {
+        let v = match self.replaying.front() {
+            Some(r) if r.step != self.steps => {
+                self.fault = Some(Fault::ReplayDiverged {
+                    step: self.steps,
+                    expected: r.step,
+                });
+                self.halted = true;
+                return None;
+            }
+            Some(_) => self.replaying.pop_front().map(|r| r.value),
+            None => self.input_queue.pop_front(),
+        };
+        if let (Some(value), Some(recording)) = (v, &mut self.recording) {
+            recording.reads.push(InputRead {
+                step: self.steps,
+                value,
+            });
+        }
+        v
+    }
+
+    fn write_output(&mut self, v: Int) {
+        self.last_output = v;
+        self.output_queue.push_back(v);
+    }
+
+    pub fn read_output(&mut self) -> Option<Int> {
+        self.output_queue.pop_front()
+    }
+
+    // True if the next instruction is an input and there is nothing queued
+    // for it to read, so stepping any further would just spin.
+    pub fn awaiting_input(&self) -> bool {
+        if let Opcode::Input = self.instruction().opcode() {
+            self.input_queue.is_empty() && self.replaying.is_empty()
+        } else {
+            false
+        }
+    }
+
+    pub fn exec_instruction(&mut self) {
+        let inst = self.instruction();
+        let opcode = inst.opcode();
+        let ip = self.ip;
+        self.mark_executed(1 + opcode.num_params());
+        let mut blocked = false;
+
+        match opcode {
+            Opcode::Input => {
+                if let Some(input) = self.read_input() {
+                    self.write_param(&inst, 1, input);
+                    self.ip += 2;
+                } else {
+                    blocked = true;
+                }
+            }
+            Opcode::Output => {
+                let src = self.read_param(&inst, 1);
+                self.write_output(src);
+                self.ip += 2;
+            }
+            Opcode::Add => {
+                let src1 = self.read_param(&inst, 1);
+                let src2 = self.read_param(&inst, 2);
+                self.write_param(&inst, 3, add(src1, src2));
+                self.ip += 4;
+            }
+            Opcode::Mul => {
+                let src1 = self.read_param(&inst, 1);
+                let src2 = self.read_param(&inst, 2);
+                self.write_param(&inst, 3, mul(src1, src2));
+                self.ip += 4;
+            }
+            Opcode::JumpIfTrue => {
+                let src = self.read_param(&inst, 1);
+                let dst = self.read_param(&inst, 2) as usize;
+                if src != 0 {
+                    self.ip = dst;
+                } else {
+                    self.ip += 3;
+                }
+            }
+            Opcode::JumpIfFalse => {
+                let src = self.read_param(&inst, 1);
+                let dst = self.read_param(&inst, 2) as usize;
+                if src == 0 {
+                    self.ip = dst;
+                } else {
+                    self.ip += 3;
+                }
+            }
+            Opcode::LessThan => {
+                let src1 = self.read_param(&inst, 1);
+                let src2 = self.read_param(&inst, 2);
+                self.write_param(&inst, 3, (src1 < src2) as Int);
+                self.ip += 4;
+            }
+            Opcode::Equals => {
+                let src1 = self.read_param(&inst, 1);
+                let src2 = self.read_param(&inst, 2);
+                self.write_param(&inst, 3, (src1 == src2) as Int);
+                self.ip += 4;
+            }
+            Opcode::RelativeBase => {
+                let src = self.read_param(&inst, 1);
+                self.rb = add(self.rb, src);
+                self.ip += 2;
+            }
+            Opcode::Halt => {
+                self.halted = true;
+            }
+        }
+        if self.fault.is_some() {
+            self.ip = ip;
+        } else if !blocked {
+            self.steps += 1;
+        }
+    }
+
+    pub fn run(&mut self) {
+        while !self.halted {
+            self.exec_instruction()
+        }
+    }
+
+    // Run until the program halts or blocks waiting for input.
+    pub fn run_until_blocked(&mut self) {
+        while !self.halted && !self.awaiting_input() {
+            self.exec_instruction()
+        }
+    }
+}
+
+pub fn load_program(prog: &str) -> Vec<Int> {
+    parse_list(prog.trim(), ',', "memory cell").unwrap_or_else(|e| panic!("{}", e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_param_mode() {
+        assert_eq!(Instruction { v: 100 }.param_mode(1), ParamMode::Immediate);
+        assert_eq!(Instruction { v: 100 }.param_mode(2), ParamMode::Position);
+        assert_eq!(Instruction { v: 100 }.param_mode(3), ParamMode::Position);
+        assert_eq!(Instruction { v: 1000 }.param_mode(1), ParamMode::Position);
+        assert_eq!(Instruction { v: 1100 }.param_mode(2), ParamMode::Immediate);
+        assert_eq!(Instruction { v: 1100 }.param_mode(3), ParamMode::Position);
+        assert_eq!(Instruction { v: 200 }.param_mode(1), ParamMode::Relative);
+    }
+
+    fn run_program(code: &str, inputs: &[Int]) -> Vec<Int> {
+        let mut c = Computer::new(load_program(code));
+        for input in inputs {
+            c.inject_input(*input);
+        }
+        c.run();
+        let mut outputs = vec![];
+        while let Some(v) = c.read_output() {
+            outputs.push(v);
+        }
+        outputs
+    }
+
+    #[test]
+    fn test_cpu() {
+        let quine = "109,1,204,-1,1001,100,1,100,1008,100,16,101,1006,101,0,99";
+        let test_data = [
+            (quine, vec![], load_program(quine)[..16].to_vec()),
+            (
+                "1102,34915192,34915192,7,4,7,99,0",
+                vec![],
+                vec![1_219_070_632_396_864],
+            ),
+            (
+                "104,1125899906842624,99",
+                vec![],
+                vec![1_125_899_906_842_624],
+            ),
+            ("3,9,8,9,10,9,4,9,99,-1,8", vec![8], vec![1]),
+            ("3,9,8,9,10,9,4,9,99,-1,8", vec![7], vec![0]),
+            ("3,3,1107,-1,8,3,4,3,99", vec![5], vec![1]),
+            ("3,12,6,12,15,1,13,14,13,4,13,99,-1,0,1,9", vec![0], vec![0]),
+        ];
+
+        for (code, inputs, expected) in &test_data {
+            assert_eq!(run_program(code, inputs), *expected);
+        }
+    }
+
+    #[test]
+    fn test_memory_grows_on_demand() {
+        let mut c = Computer::new(load_program("1101,2,3,1000,4,1000,4,2000,99"));
+        let snapshot = c.clone();
+        c.run();
+        assert_eq!(c.read_output(), Some(5));
+        assert_eq!(c.read_output(), Some(0));
+        assert_eq!(c.mem.len(), 1001);
+        assert_eq!(snapshot.mem.len(), 9);
+    }
+
+    #[test]
+    fn test_run_until_blocked() {
+        let mut c = Computer::new(load_program("3,11,4,11,3,11,4,11,99"));
+        c.run_until_blocked();
+        assert!(c.awaiting_input());
+        c.inject_input(7);
+        c.run_until_blocked();
+        assert_eq!(c.read_output(), Some(7));
+        assert!(c.awaiting_input());
+        c.inject_input(9);
+        c.run_until_blocked();
+        assert_eq!(c.read_output(), Some(9));
+        assert!(c.halted);
+    }
+}
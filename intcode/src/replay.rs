@@ -0,0 +1,139 @@
+use crate::Int;
+use failure::{bail, format_err, Error};
+use std::fmt;
+
+// A value consumed by an input instruction, and the step count when it was
+// read.
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub struct InputRead {
+    pub step: u64,
+    pub value: Int,
+}
+
+// A replay file has one "STEP VALUE" line per input read. Blank lines and
+// lines starting with `#` are ignored.
+#[derive(Debug, Clone, Default, Eq, PartialEq)]
+pub struct Replay {
+    pub reads: Vec<InputRead>,
+}
+
+impl fmt::Display for Replay {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for r in &self.reads {
+            writeln!(f, "{} {}", r.step, r.value)?;
+        }
+        Ok(())
+    }
+}
+
+impl Replay {
+    pub fn parse(s: &str) -> Result<Replay, Error> {
+        let mut reads: Vec<InputRead> = vec![];
+        for (i, line) in s.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let read =
+                Self::parse_read(line).map_err(|e| format_err!("Replay line {}: {}", i + 1, e))?;
+            if let Some(last) = reads.last() {
+                if read.step <= last.step {
+                    bail!("Replay line {}: steps must increase", i + 1);
+                }
+            }
+            reads.push(read);
+        }
+        Ok(Replay { reads })
+    }
+
+    fn parse_read(line: &str) -> Result<InputRead, Error> {
+        let fields: Vec<&str> = line.split_whitespace().collect();
+        if fields.len() != 2 {
+            bail!("expected STEP VALUE, got {:?}", line);
+        }
+        Ok(InputRead {
+            step: fields[0].parse()?,
+            value: fields[1].parse()?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{load_program, Computer, Fault};
+
+    // Echoes inputs until it reads a zero, after a loop whose length depends
+    // on the first input.
+    const PROGRAM: &str = "3,100,1001,100,-1,100,1005,100,2,3,101,4,101,1005,101,9,99";
+
+    fn outputs(c: &mut Computer) -> Vec<Int> {
+        let mut outputs = vec![];
+        while let Some(v) = c.read_output() {
+            outputs.push(v);
+        }
+        outputs
+    }
+
+    #[test]
+    fn test_record_and_replay() {
+        let mut c = Computer::new(load_program(PROGRAM));
+        c.recording = Some(Replay::default());
+        for input in &[3, 7, 8, 0] {
+            c.run_until_blocked();
+            c.inject_input(*input);
+        }
+        c.run();
+        let recorded = outputs(&mut c);
+        let replay = c.recording.take().unwrap();
+        assert_eq!(
+            replay.reads,
+            vec![
+                InputRead { step: 0, value: 3 },
+                InputRead { step: 7, value: 7 },
+                InputRead { step: 10, value: 8 },
+                InputRead { step: 13, value: 0 },
+            ]
+        );
+
+        let replay = Replay::parse(&format!("# recorded\n\n{}", replay)).unwrap();
+        let mut c = Computer::new(load_program(PROGRAM));
+        c.replay(&replay);
+        c.run();
+        assert!(c.fault.is_none());
+        assert_eq!(outputs(&mut c), recorded);
+        assert_eq!(recorded, vec![7, 8, 0]);
+    }
+
+    #[test]
+    fn test_replay_diverged() {
+        let replay = Replay::parse("0 3\n5 7\n").unwrap();
+        let mut c = Computer::new(load_program(PROGRAM));
+        c.replay(&replay);
+        c.run();
+        assert_eq!(
+            c.fault,
+            Some(Fault::ReplayDiverged {
+                step: 7,
+                expected: 5
+            })
+        );
+        assert_eq!(c.ip, 9);
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        let test_data = [
+            ("1 2 3", "Replay line 1: expected STEP VALUE, got \"1 2 3\""),
+            (
+                "# header\n1 x",
+                "Replay line 2: invalid digit found in string",
+            ),
+            ("5 1\n5 2", "Replay line 2: steps must increase"),
+        ];
+
+        for (s, expected) in &test_data {
+            assert_eq!(Replay::parse(s).unwrap_err().to_string(), *expected);
+        }
+    }
+}
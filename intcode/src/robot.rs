@@ -0,0 +1,204 @@
+use crate::{Computer, Int};
+use common::grid::Point;
+use std::collections::{HashMap, HashSet};
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum Colour {
+    Black,
+    White,
+}
+
+impl Colour {
+    fn from_int(v: Int) -> Colour {
+        match v {
+            0 => Colour::Black,
+            1 => Colour::White,
+            _ => panic!("Invalid colour: {}", v),
+        }
+    }
+
+    fn to_int(self) -> Int {
+        match self {
+            Colour::Black => 0,
+            Colour::White => 1,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+enum Direction {
+    Up,
+    Right,
+    Down,
+    Left,
+}
+
+impl Direction {
+    fn turn(self, v: Int) -> Direction {
+        use Direction::*;
+        match (v, self) {
+            (0, Up) => Left,
+            (0, Left) => Down,
+            (0, Down) => Right,
+            (0, Right) => Up,
+            (1, Up) => Right,
+            (1, Right) => Down,
+            (1, Down) => Left,
+            (1, Left) => Up,
+            _ => panic!("Invalid turn: {}", v),
+        }
+    }
+
+    fn step(self, p: Point) -> Point {
+        use Direction::*;
+        match self {
+            Up => Point { x: p.x, y: p.y + 1 },
+            Right => Point { x: p.x + 1, y: p.y },
+            Down => Point { x: p.x, y: p.y - 1 },
+            Left => Point { x: p.x - 1, y: p.y },
+        }
+    }
+}
+
+// Unpainted panels are black.
+#[derive(Default)]
+pub struct Hull {
+    panels: HashMap<Point, Colour>,
+    painted: HashSet<Point>,
+}
+
+impl Hull {
+    pub fn colour(&self, p: Point) -> Colour {
+        *self.panels.get(&p).unwrap_or(&Colour::Black)
+    }
+
+    pub fn paint(&mut self, p: Point, colour: Colour) {
+        self.panels.insert(p, colour);
+        self.painted.insert(p);
+    }
+
+    // Every panel that has been painted at least once, whatever its colour now.
+    pub fn painted(&self) -> &HashSet<Point> {
+        &self.painted
+    }
+
+    // Render the painted area, top row first, with white panels as `*`.
+    pub fn render(&self) -> String {
+        let mut s = String::new();
+        if self.painted.is_empty() {
+            return s;
+        }
+        let min_x = self.painted.iter().map(|p| p.x).min().unwrap();
+        let max_x = self.painted.iter().map(|p| p.x).max().unwrap();
+        let min_y = self.painted.iter().map(|p| p.y).min().unwrap();
+        let max_y = self.painted.iter().map(|p| p.y).max().unwrap();
+
+        for y in (min_y..=max_y).rev() {
+            for x in min_x..=max_x {
+                s += match self.colour(Point { x, y }) {
+                    Colour::Black => " ",
+                    Colour::White => "*",
+                }
+            }
+            s += "\n";
+        }
+        s
+    }
+}
+
+pub struct Robot {
+    computer: Computer,
+    pos: Point,
+    dir: Direction,
+    pub hull: Hull,
+}
+
+impl Robot {
+    pub fn new(computer: Computer) -> Self {
+        Robot {
+            computer,
+            pos: Point { x: 0, y: 0 },
+            dir: Direction::Up,
+            hull: Hull::default(),
+        }
+    }
+
+    pub fn position(&self) -> Point {
+        self.pos
+    }
+
+    // Feed the current panel colour to the program and apply every
+    // (paint, turn) pair it produces before it next blocks or halts.
+    pub fn step(&mut self) {
+        self.computer
+            .inject_input(self.hull.colour(self.pos).to_int());
+        self.computer.run_until_blocked();
+
+        while let Some(paint) = self.computer.read_output() {
+            let turn = self
+                .computer
+                .read_output()
+                .unwrap_or_else(|| panic!("Paint output {} without a turn", paint));
+            self.hull.paint(self.pos, Colour::from_int(paint));
+            self.dir = self.dir.turn(turn);
+            self.pos = self.dir.step(self.pos);
+        }
+    }
+
+    pub fn run(mut self) -> Hull {
+        while !self.computer.halted {
+            self.step();
+        }
+        self.hull
+    }
+}
+
+pub fn paint_hull(program: Vec<Int>, start: Colour) -> Hull {
+    let mut robot = Robot::new(Computer::new(program));
+    robot.hull.panels.insert(robot.pos, start);
+    robot.run()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::load_program;
+
+    // Reads a colour before emitting each (paint, turn) pair, like the real
+    // robot brain, but ignores it.
+    fn scripted_program(moves: &[(Int, Int)]) -> Vec<Int> {
+        let code = moves
+            .iter()
+            .map(|(paint, turn)| format!("3,1000,104,{},104,{},", paint, turn))
+            .collect::<String>()
+            + "99";
+        load_program(&code)
+    }
+
+    #[test]
+    fn test_paint_hull() {
+        let moves = [(1, 0), (0, 0), (1, 0), (1, 0), (0, 1), (1, 0), (1, 0)];
+        let hull = paint_hull(scripted_program(&moves), Colour::Black);
+
+        assert_eq!(hull.painted().len(), 6);
+        assert_eq!(hull.colour(Point { x: 0, y: 0 }), Colour::Black);
+        assert_eq!(hull.colour(Point { x: 1, y: 1 }), Colour::White);
+        assert_eq!(hull.render(), "  *\n  *\n** \n");
+    }
+
+    #[test]
+    fn test_robot_reads_panel_colour() {
+        // Echo the panel colour back as the paint colour and always turn right,
+        // so a white starting panel stays white and everything else is black.
+        let code = "3,100,4,100,104,1,1105,1,0";
+        let mut robot = Robot::new(Computer::new(load_program(code)));
+        robot.hull.paint(Point { x: 0, y: 0 }, Colour::White);
+        for _ in 0..4 {
+            robot.step();
+        }
+
+        assert_eq!(robot.position(), Point { x: 0, y: 0 });
+        assert_eq!(robot.hull.painted().len(), 4);
+        assert_eq!(robot.hull.render(), "* \n  \n");
+    }
+}
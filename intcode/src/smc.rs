@@ -0,0 +1,183 @@
+use crate::{Computer, Int};
+use std::collections::HashSet;
+use std::fmt;
+
+// A write by the instruction at `ip` to a cell that had already been
+// executed, as either an opcode or a parameter.
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub struct CodeWrite {
+    pub ip: usize,
+    pub addr: usize,
+    pub old: Int,
+    pub new: Int,
+}
+
+impl fmt::Display for CodeWrite {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "ip {} wrote {} to {} (was {})",
+            self.ip, self.new, self.addr, self.old
+        )
+    }
+}
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum CellKind {
+    // Executed and never overwritten afterwards.
+    Code,
+    // Executed, then overwritten by the program.
+    ModifiedCode,
+    // Never executed.
+    Data,
+}
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub struct Region {
+    pub start: usize,
+    pub end: usize, // exclusive
+    pub kind: CellKind,
+}
+
+// Split the computer's memory into runs of cells of the same kind, based on
+// what it has executed so far.
+pub fn regions(c: &Computer) -> Vec<Region> {
+    let modified: HashSet<usize> = c.code_writes.iter().map(|w| w.addr).collect();
+    let kind = |addr| {
+        if !c.executed(addr) {
+            CellKind::Data
+        } else if modified.contains(&addr) {
+            CellKind::ModifiedCode
+        } else {
+            CellKind::Code
+        }
+    };
+
+    let mut regions: Vec<Region> = vec![];
+    for addr in 0..c.mem.len() {
+        let kind = kind(addr);
+        match regions.last_mut() {
+            Some(r) if r.kind == kind => r.end = addr + 1,
+            _ => regions.push(Region {
+                start: addr,
+                end: addr + 1,
+                kind,
+            }),
+        }
+    }
+    regions
+}
+
+pub fn summary(c: &Computer) -> String {
+    let mut s = String::new();
+    for r in regions(c) {
+        let kind = match r.kind {
+            CellKind::Code => "code",
+            CellKind::ModifiedCode => "modified code",
+            CellKind::Data => "data",
+        };
+        s += &format!("{:>6}..{:<6} {}\n", r.start, r.end, kind);
+    }
+    if !c.code_writes.is_empty() {
+        s += "writes to code:\n";
+        for w in &c.code_writes {
+            s += &format!("  {}\n", w);
+        }
+    }
+    s
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{load_program, Fault};
+
+    // Overwrites the immediate parameter of its first instruction, then
+    // jumps back to run it again. The second pass adds 10 instead of 1.
+    const SELF_MODIFYING: &str = "1001,16,1,16,1101,0,10,2,1007,16,5,17,1005,17,0,99,0,0";
+
+    #[test]
+    fn test_regions() {
+        let mut c = Computer::new(load_program("1101,2,3,7,4,7,99,0"));
+        c.run();
+        assert_eq!(
+            regions(&c),
+            vec![
+                Region {
+                    start: 0,
+                    end: 7,
+                    kind: CellKind::Code
+                },
+                Region {
+                    start: 7,
+                    end: 8,
+                    kind: CellKind::Data
+                },
+            ]
+        );
+        assert!(c.code_writes.is_empty());
+    }
+
+    #[test]
+    fn test_code_writes_reported() {
+        let mut c = Computer::new(load_program(SELF_MODIFYING));
+        c.run();
+        assert!(c.fault.is_none());
+        assert_eq!(c.read_mem(16), 11);
+        assert_eq!(
+            c.code_writes,
+            vec![
+                CodeWrite {
+                    ip: 4,
+                    addr: 2,
+                    old: 1,
+                    new: 10
+                },
+                CodeWrite {
+                    ip: 4,
+                    addr: 2,
+                    old: 10,
+                    new: 10
+                },
+            ]
+        );
+        assert_eq!(
+            summary(&c),
+            "     0..2      code\n     \
+             2..3      modified code\n     \
+             3..16     code\n    \
+             16..18     data\n\
+             writes to code:\n  \
+             ip 4 wrote 10 to 2 (was 1)\n  \
+             ip 4 wrote 10 to 2 (was 10)\n"
+        );
+    }
+
+    #[test]
+    fn test_fault_on_code_write() {
+        let mut c = Computer::new(load_program(SELF_MODIFYING));
+        c.fault_on_code_write = true;
+        c.run();
+        let write = CodeWrite {
+            ip: 4,
+            addr: 2,
+            old: 1,
+            new: 10,
+        };
+        assert_eq!(c.fault, Some(Fault::WriteToCode(write)));
+        assert!(c.halted);
+        assert_eq!(c.ip, 4);
+        assert_eq!(c.read_mem(2), 1);
+    }
+
+    #[test]
+    fn test_writes_before_execution_allowed() {
+        // Patches the parameter of the output instruction before reaching it.
+        let mut c = Computer::new(load_program("1101,0,42,5,104,0,99"));
+        c.fault_on_code_write = true;
+        c.run();
+        assert!(c.fault.is_none());
+        assert_eq!(c.read_output(), Some(42));
+        assert!(c.code_writes.is_empty());
+    }
+}
@@ -0,0 +1,281 @@
+// Runs the same programs on each of the copies of `Computer` that grew up in
+// the day crates, plus this crate's, and checks that they agree.
+//
+// Some generated programs are given fewer inputs than they read. The others
+// block waiting for more, but the day 5 copy panics instead, and that
+// difference is checked for rather than compared.
+
+use proptest::prelude::*;
+use std::panic;
+
+#[allow(dead_code)]
+#[path = "../../2019/day5/src/computer.rs"]
+mod day5;
+
+#[allow(dead_code)]
+#[path = "../../2019/day7/src/computer.rs"]
+mod day7;
+
+#[allow(dead_code)]
+#[path = "../../2019/day9/src/computer.rs"]
+mod day9;
+
+// Programs only jump forwards, so they halt well within this many steps
+// unless they block waiting for input, when they spin without changing.
+const MAX_STEPS: usize = 100;
+
+#[derive(Debug, Clone)]
+enum Param {
+    Position(usize),
+    Immediate(i32),
+}
+
+#[derive(Debug, Clone)]
+enum Op {
+    // Add, Mul, LessThan and Equals
+    Arith(i32, Param, Param),
+    Input,
+    Output(Param),
+    // JumpIfTrue and JumpIfFalse, skipping forward over some instructions
+    Jump(i32, Param, usize),
+}
+
+impl Op {
+    fn len(&self) -> usize {
+        match self {
+            Op::Arith(..) => 4,
+            Op::Input | Op::Output(_) => 2,
+            Op::Jump(..) => 3,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+struct Program {
+    mem: Vec<i32>,
+    inputs: Vec<i32>,
+}
+
+fn param(max_addr: usize) -> impl Strategy<Value = Param> {
+    prop_oneof![
+        (0..max_addr).prop_map(Param::Position),
+        (-20..20).prop_map(Param::Immediate),
+    ]
+}
+
+fn op(max_addr: usize) -> impl Strategy<Value = Op> {
+    prop_oneof![
+        (
+            prop_oneof![Just(1), Just(2), Just(7), Just(8)],
+            param(max_addr),
+            param(max_addr)
+        )
+            .prop_map(|(opcode, a, b)| Op::Arith(opcode, a, b)),
+        Just(Op::Input),
+        param(max_addr).prop_map(Op::Output),
+        (prop_oneof![Just(5), Just(6)], param(max_addr), 0..4_usize)
+            .prop_map(|(opcode, cond, skip)| Op::Jump(opcode, cond, skip)),
+    ]
+}
+
+// Lay out the instructions, then a halt, then one result cell per
+// instruction, then some initial data. Every write goes to its own result
+// cell, so code is never overwritten and every value the program computes
+// survives to the final memory. Jumps only go forwards, so every program
+// terminates. The program is given `missing` fewer inputs than it reads.
+fn assemble(ops: &[Op], data: &[i32], inputs: &[i32], missing: usize) -> Program {
+    let mut starts = vec![];
+    let mut addr = 0;
+    for op in ops {
+        starts.push(addr);
+        addr += op.len();
+    }
+    let halt = addr;
+    let results = halt + 1;
+    let size = results + ops.len() + data.len();
+
+    let encode = |p: &Param, mode_digit: i32| match p {
+        Param::Position(a) => ((a % size) as i32, 0),
+        Param::Immediate(v) => (*v, mode_digit),
+    };
+
+    let mut mem = vec![];
+    let mut num_inputs: usize = 0;
+    for (i, op) in ops.iter().enumerate() {
+        let result = (results + i) as i32;
+        match op {
+            Op::Arith(opcode, a, b) => {
+                let (a, ma) = encode(a, 100);
+                let (b, mb) = encode(b, 1000);
+                mem.extend(&[opcode + ma + mb, a, b, result]);
+            }
+            Op::Input => {
+                mem.extend(&[3, result]);
+                num_inputs += 1;
+            }
+            Op::Output(a) => {
+                let (a, ma) = encode(a, 100);
+                mem.extend(&[4 + ma, a]);
+            }
+            Op::Jump(opcode, cond, skip) => {
+                let (c, mc) = encode(cond, 100);
+                let target = *starts.get(i + 1 + skip).unwrap_or(&halt) as i32;
+                mem.extend(&[opcode + mc + 1000, c, target]);
+            }
+        }
+    }
+    mem.push(99);
+    mem.extend(vec![0; ops.len()]);
+    mem.extend(data);
+
+    Program {
+        mem,
+        inputs: inputs
+            .iter()
+            .cycle()
+            .take(num_inputs.saturating_sub(missing))
+            .cloned()
+            .collect(),
+    }
+}
+
+fn program() -> impl Strategy<Value = Program> {
+    (
+        prop::collection::vec(-20..20_i32, 1..8),
+        prop::collection::vec(-20..20_i32, 1..4),
+        prop_oneof![3 => Just(0), 1 => 1..3_usize],
+    )
+        .prop_flat_map(|(data, inputs, missing)| {
+            // Positions may point anywhere, including into the code.
+            let max_addr = 100;
+            prop::collection::vec(op(max_addr), 1..16)
+                .prop_map(move |ops| assemble(&ops, &data, &inputs, missing))
+        })
+}
+
+// Whether the computer halted, rather than blocking, its outputs and its final
+// memory.
+type Run = (bool, Vec<i64>, Vec<i64>);
+
+fn widen(v: &[i32]) -> Vec<i64> {
+    v.iter().map(|x| i64::from(*x)).collect()
+}
+
+// Step a computer until it halts, or for long enough that it must be blocked.
+macro_rules! run {
+    ($c:expr) => {{
+        for _ in 0..MAX_STEPS {
+            if $c.halted {
+                break;
+            }
+            $c.exec_instruction();
+        }
+    }};
+}
+
+fn run_day5(p: &Program) -> Run {
+    let mut c = day5::Computer::new(p.mem.clone(), p.inputs.clone());
+    run!(c);
+    (c.halted, widen(&c.outputs), widen(&c.mem))
+}
+
+fn run_day7(p: &Program) -> Run {
+    let mut c = day7::Computer::new(p.mem.clone());
+    for input in &p.inputs {
+        c.inject_input(*input);
+    }
+    run!(c);
+    let mut outputs = vec![];
+    while let Some(v) = c.read_output() {
+        outputs.push(v);
+    }
+    (c.halted, widen(&outputs), widen(&c.mem))
+}
+
+fn run_day9(p: &Program) -> Run {
+    let mut c = day9::Computer::new(widen(&p.mem));
+    for input in &p.inputs {
+        c.inject_input(i64::from(*input));
+    }
+    run!(c);
+    let mut outputs = vec![];
+    while let Some(v) = c.read_output() {
+        outputs.push(v);
+    }
+    (c.halted, outputs, c.mem)
+}
+
+fn run_intcode(p: &Program) -> Run {
+    let mut c = intcode::Computer::new(widen(&p.mem));
+    for input in &p.inputs {
+        c.inject_input(i64::from(*input));
+    }
+    run!(c);
+    let mut outputs = vec![];
+    while let Some(v) = c.read_output() {
+        outputs.push(v);
+    }
+    (c.halted, outputs, c.mem)
+}
+
+fn fits_i32(run: &Run) -> bool {
+    run.1
+        .iter()
+        .chain(run.2.iter())
+        .all(|v| *v >= i64::from(i32::MIN) && *v <= i64::from(i32::MAX))
+}
+
+proptest! {
+    #[test]
+    fn test_computers_agree(p in program()) {
+        // Days 5 and 7 use 32-bit cells, so only compare programs whose
+        // results fit. Each result cell is written once, so checking the
+        // 64-bit computer's final state covers every intermediate value.
+        let reference = panic::catch_unwind(|| run_intcode(&p));
+        prop_assume!(reference.as_ref().is_ok_and(fits_i32));
+        let reference = reference.unwrap();
+
+        prop_assert_eq!(&run_day9(&p), &reference, "day 9");
+        prop_assert_eq!(&run_day7(&p), &reference, "day 7");
+        if reference.0 {
+            prop_assert_eq!(&run_day5(&p), &reference, "day 5");
+        } else {
+            prop_assert!(panic::catch_unwind(|| run_day5(&p)).is_err(), "day 5");
+        }
+    }
+}
+
+#[test]
+fn test_day5_reads_inputs_in_order() {
+    let p = Program {
+        mem: vec![3, 9, 3, 10, 4, 9, 4, 10, 99, 0, 0],
+        inputs: vec![1, 2],
+    };
+    assert_eq!(run_day5(&p), run_intcode(&p));
+}
+
+#[test]
+fn test_day5_immediate_second_param() {
+    let p = Program {
+        mem: vec![1001, 7, 7, 7, 4, 7, 99, 5],
+        inputs: vec![],
+    };
+    assert_eq!(run_day5(&p).1, vec![12]);
+    assert_eq!(run_day5(&p), run_intcode(&p));
+}
+
+#[test]
+fn test_out_of_input() {
+    let p = Program {
+        mem: vec![3, 7, 4, 7, 3, 8, 99, 0, 0],
+        inputs: vec![5],
+    };
+    let reference = run_intcode(&p);
+    assert_eq!(
+        reference,
+        (false, vec![5], vec![3, 7, 4, 7, 3, 8, 99, 5, 0])
+    );
+    assert_eq!(run_day9(&p), reference);
+    assert_eq!(run_day7(&p), reference);
+    assert!(panic::catch_unwind(|| run_day5(&p)).is_err());
+}
@@ -0,0 +1,22 @@
+use intcode::fuzz::{execute, Case};
+use std::fs;
+use std::path::Path;
+
+// Each file under `tests/fuzz` is a minimised case written by `intcode-fuzz`
+// together with the outcome it produced. Replaying them catches any change in
+// how the computer handles those inputs, deliberate or not; a deliberate one
+// means updating or deleting the file.
+#[test]
+fn test_fuzz_regressions() {
+    let dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fuzz");
+    let mut count = 0;
+    for entry in fs::read_dir(dir).unwrap() {
+        let path = entry.unwrap().path();
+        let (case, expected) = Case::parse_regression(&fs::read_to_string(&path).unwrap())
+            .unwrap_or_else(|e| panic!("{}: {}", path.display(), e));
+        let (outcome, _) = execute(&case, 10_000);
+        assert_eq!(outcome, expected, "{}", path.display());
+        count += 1;
+    }
+    assert!(count > 0);
+}
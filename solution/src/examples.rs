@@ -0,0 +1,96 @@
+use crate::inputs::read_input;
+use crate::Solution;
+use failure::{format_err, Error};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+// Examples from the puzzle statements live in `examples/<year>/<day>/` as
+// `<name>.in`, with the expected answer to either part, or both, in
+// `<name>.part1.out` and `<name>.part2.out`.
+pub struct Example {
+    pub name: String,
+    pub part: u32,
+    pub input: PathBuf,
+    pub output: PathBuf,
+}
+
+pub fn examples_dir(year: u32, day: u32) -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("../examples")
+        .join(year.to_string())
+        .join(day.to_string())
+}
+
+// All the examples with an expected answer in a directory, in name order.
+pub fn find(dir: &Path) -> Result<Vec<Example>, Error> {
+    let entries =
+        fs::read_dir(dir).map_err(|e| format_err!("Failed to read {}: {}", dir.display(), e))?;
+    let mut examples = vec![];
+    for entry in entries {
+        let file = entry?.file_name().into_string().unwrap_or_default();
+        let name = match file.strip_suffix(".in") {
+            Some(name) => name,
+            None => continue,
+        };
+        for part in 1..=2 {
+            let output = dir.join(format!("{}.part{}.out", name, part));
+            if output.exists() {
+                examples.push(Example {
+                    name: name.to_string(),
+                    part,
+                    input: dir.join(&file),
+                    output,
+                });
+            }
+        }
+    }
+    examples.sort_by(|a, b| (&a.name, a.part).cmp(&(&b.name, b.part)));
+    Ok(examples)
+}
+
+fn check<S: Solution>(example: &Example) -> Result<(), Error> {
+    let input = read_input(&example.input)?;
+    let expected = read_input(&example.output)?;
+    let run = crate::run::<S>(&input, example.part)?;
+    let answer = run.answer.to_string();
+    if answer.trim_end() != expected {
+        return Err(format_err!(
+            "expected:\n{}\ngot:\n{}",
+            expected,
+            answer.trim_end()
+        ));
+    }
+    Ok(())
+}
+
+// Run a solution against every example in a directory, panicking with all
+// the ones it gets wrong.
+pub fn check_all<S: Solution>(dir: &Path) {
+    let examples = find(dir).unwrap();
+    assert!(!examples.is_empty(), "No examples in {}", dir.display());
+    let failures: Vec<String> = examples
+        .iter()
+        .filter_map(|example| {
+            let e = check::<S>(example).err()?;
+            Some(format!("{} part {}: {}", example.name, example.part, e))
+        })
+        .collect();
+    if !failures.is_empty() {
+        panic!(
+            "{} examples failed:\n{}",
+            failures.len(),
+            failures.join("\n")
+        );
+    }
+}
+
+// Adds a test that runs a day's solution against all its examples.
+#[macro_export]
+macro_rules! example_tests {
+    ($solution:ty, $year:expr, $day:expr) => {
+        #[test]
+        fn test_examples() {
+            $crate::examples::check_all::<$solution>(&$crate::examples::examples_dir($year, $day));
+        }
+    };
+}
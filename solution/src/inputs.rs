@@ -0,0 +1,116 @@
+use failure::{format_err, Error};
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+// Puzzle inputs live under a root directory as `<year>/<day>.txt`, with any
+// other accounts' inputs for the same day alongside as
+// `<year>/<day>.<name>.txt`. Nothing is ever fetched.
+pub struct Inputs {
+    root: PathBuf,
+}
+
+// Read a puzzle input without its trailing whitespace, as solutions expect.
+pub fn read_input(path: &Path) -> Result<String, Error> {
+    let input = fs::read_to_string(path)
+        .map_err(|e| format_err!("Failed to read {}: {}", path.display(), e))?;
+    Ok(input.trim_end().to_string())
+}
+
+impl Inputs {
+    pub fn new(root: impl Into<PathBuf>) -> Inputs {
+        Inputs { root: root.into() }
+    }
+
+    // The root set by $ADVENT_INPUTS, or else the workspace's `inputs`
+    // directory.
+    pub fn from_env() -> Inputs {
+        match env::var_os("ADVENT_INPUTS") {
+            Some(root) => Inputs::new(root),
+            None => Inputs::new(Path::new(env!("CARGO_MANIFEST_DIR")).join("../inputs")),
+        }
+    }
+
+    pub fn path(&self, year: u32, day: u32, name: Option<&str>) -> PathBuf {
+        let file = match name {
+            Some(name) => format!("{}.{}.txt", day, name),
+            None => format!("{}.txt", day),
+        };
+        self.root.join(year.to_string()).join(file)
+    }
+
+    // The names of the extra inputs there are for a day.
+    pub fn names(&self, year: u32, day: u32) -> Vec<String> {
+        let prefix = format!("{}.", day);
+        let mut names: Vec<String> = fs::read_dir(self.root.join(year.to_string()))
+            .into_iter()
+            .flatten()
+            .filter_map(|entry| {
+                let file = entry.ok()?.file_name().into_string().ok()?;
+                let name = file.strip_prefix(&prefix)?.strip_suffix(".txt")?;
+                Some(name.to_string()).filter(|name| !name.is_empty())
+            })
+            .collect();
+        names.sort();
+        names
+    }
+
+    pub fn read(&self, year: u32, day: u32, name: Option<&str>) -> Result<String, Error> {
+        let path = self.path(year, day, name);
+        if !path.exists() {
+            let names = self.names(year, day);
+            return Err(format_err!(
+                "No input for {} day {}{}: expected {}{}",
+                year,
+                day,
+                name.map_or(String::new(), |name| format!(" ({})", name)),
+                path.display(),
+                if names.is_empty() {
+                    String::new()
+                } else {
+                    format!(" (other inputs: {})", names.join(", "))
+                }
+            ));
+        }
+        read_input(&path)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_inputs() {
+        let root = env::temp_dir().join(format!("advent-inputs-{}", std::process::id()));
+        fs::create_dir_all(root.join("2019")).unwrap();
+        fs::write(root.join("2019/3.txt"), "R8,U5\nU7,R6\n\n").unwrap();
+        fs::write(root.join("2019/3.alice.txt"), "R1\nU1").unwrap();
+        fs::write(root.join("2019/3.bob.txt"), "L1\nD1").unwrap();
+        let inputs = Inputs::new(&root);
+
+        assert_eq!(inputs.read(2019, 3, None).unwrap(), "R8,U5\nU7,R6");
+        assert_eq!(inputs.read(2019, 3, Some("alice")).unwrap(), "R1\nU1");
+        assert_eq!(inputs.names(2019, 3), vec!["alice", "bob"]);
+        assert_eq!(
+            inputs
+                .read(2019, 3, Some("carol"))
+                .err()
+                .unwrap()
+                .to_string(),
+            format!(
+                "No input for 2019 day 3 (carol): expected {} (other inputs: alice, bob)",
+                root.join("2019/3.carol.txt").display()
+            )
+        );
+        assert_eq!(
+            inputs.read(2019, 4, None).err().unwrap().to_string(),
+            format!(
+                "No input for 2019 day 4: expected {}",
+                root.join("2019/4.txt").display()
+            )
+        );
+
+        fs::remove_dir_all(&root).unwrap();
+    }
+}
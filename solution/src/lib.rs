@@ -0,0 +1,77 @@
+use failure::{bail, Error};
+use std::fmt;
+use std::time::{Duration, Instant};
+
+pub mod examples;
+pub mod inputs;
+
+// Both parts of one day's puzzle. The input is parsed once, from the puzzle
+// input file without trailing whitespace, and shared by both parts.
+pub trait Solution {
+    type Input;
+
+    fn parse(input: &str) -> Result<Self::Input, Error>;
+    fn part1(input: &Self::Input) -> Answer;
+    fn part2(input: &Self::Input) -> Answer;
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum Answer {
+    Int(i64),
+    // Answers that aren't numbers, like rendered images.
+    Text(String),
+}
+
+impl fmt::Display for Answer {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Answer::Int(v) => write!(f, "{}", v),
+            Answer::Text(s) => write!(f, "{}", s),
+        }
+    }
+}
+
+macro_rules! answer_from_int {
+    ($($t:ty),*) => {
+        $(
+            impl From<$t> for Answer {
+                fn from(v: $t) -> Answer {
+                    Answer::Int(v as i64)
+                }
+            }
+        )*
+    };
+}
+
+answer_from_int!(i32, u32, i64, usize);
+
+impl From<String> for Answer {
+    fn from(s: String) -> Answer {
+        Answer::Text(s)
+    }
+}
+
+pub struct Run {
+    pub answer: Answer,
+    pub parse_time: Duration,
+    pub solve_time: Duration,
+}
+
+// Parse the input and solve one part of it, timing each phase.
+pub fn run<S: Solution>(input: &str, part: u32) -> Result<Run, Error> {
+    let start = Instant::now();
+    let parsed = S::parse(input)?;
+    let parse_time = start.elapsed();
+
+    let start = Instant::now();
+    let answer = match part {
+        1 => S::part1(&parsed),
+        2 => S::part2(&parsed),
+        _ => bail!("Invalid part: {}", part),
+    };
+    Ok(Run {
+        answer,
+        parse_time,
+        solve_time: start.elapsed(),
+    })
+}